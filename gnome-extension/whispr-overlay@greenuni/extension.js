@@ -29,6 +29,7 @@ export default class WhisprOverlayExtension extends Extension {
   enable() {
     this._bars = [];
     this._recording = false;
+    this._micWarm = false;
     this._startedAtMs = null;
     this._level = 0;
     this._levelTarget = 0;
@@ -314,6 +315,8 @@ export default class WhisprOverlayExtension extends Extension {
 
   _loadOverlayState() {
     let recording = false;
+    let paused = false;
+    let micWarm = false;
     let startedAtMs = null;
     let level = 0;
     let updatedAtMs = null;
@@ -324,6 +327,8 @@ export default class WhisprOverlayExtension extends Extension {
         const text = new TextDecoder('utf-8').decode(contents);
         const data = JSON.parse(text);
         recording = Boolean(data.recording);
+        paused = recording && Boolean(data.paused);
+        micWarm = Boolean(data.mic_warm);
         if (Number.isFinite(data.started_at_ms)) {
           startedAtMs = data.started_at_ms;
         }
@@ -350,22 +355,29 @@ export default class WhisprOverlayExtension extends Extension {
     }
 
     this._levelTarget = level;
+    this._micWarm = micWarm;
 
     if (recording) {
-      this._showOverlay(startedAtMs);
+      this._showOverlay(startedAtMs, paused);
     } else {
       this._hideOverlay();
     }
   }
 
-  _showOverlay(startedAtMs) {
+  _showOverlay(startedAtMs, paused) {
     this._recording = true;
     this._startedAtMs = startedAtMs;
+    this._statusLabel.text = paused ? 'Paused' : 'Recording';
     this._timeLabel.visible = Boolean(startedAtMs);
     this._overlay.show();
     this._positionOverlay();
     this._startMeter();
-    this._startClock();
+    if (paused) {
+      // started_at_ms only moves past a pause once it ends, so hold the clock meanwhile.
+      this._stopClock();
+    } else {
+      this._startClock();
+    }
     this._updateIndicator();
     this._refreshMenu();
   }
@@ -454,6 +466,7 @@ export default class WhisprOverlayExtension extends Extension {
     this._indicatorIcon.remove_style_class_name('whispr-indicator-recording');
     this._indicatorIcon.remove_style_class_name('whispr-indicator-success');
     this._indicatorIcon.remove_style_class_name('whispr-indicator-error');
+    this._indicatorIcon.remove_style_class_name('whispr-indicator-warm');
 
     if (this._recording) {
       this._indicatorIcon.icon_name = 'media-record-symbolic';
@@ -477,6 +490,10 @@ export default class WhisprOverlayExtension extends Extension {
     }
 
     this._indicatorIcon.icon_name = 'audio-input-microphone-symbolic';
+    if (this._micWarm) {
+      // Pre-roll keeps the microphone open between recordings.
+      this._indicatorIcon.add_style_class_name('whispr-indicator-warm');
+    }
     this._clearStatusTimer();
   }
 
@@ -21,6 +21,8 @@ const TRAY_ID: &str = "main";
 #[cfg(target_os = "windows")]
 const MENU_TOGGLE_ID: &str = "tray_toggle";
 #[cfg(target_os = "windows")]
+const MENU_PAUSE_ID: &str = "tray_pause";
+#[cfg(target_os = "windows")]
 const MENU_PASTE_ID: &str = "tray_paste_last";
 #[cfg(target_os = "windows")]
 const MENU_OPEN_ID: &str = "tray_open";
@@ -80,8 +82,12 @@ pub fn refresh_tray(app: &AppHandle, state: &Mutex<AppState>) {
 
     if let Some(tray) = app.tray_by_id(TRAY_ID) {
         let _ = tray.set_menu(Some(menu));
-        let tooltip = if guard.recording {
+        let tooltip = if guard.recording_paused {
+            Some("Whispr — Paused")
+        } else if guard.recording {
             Some("Whispr — Recording")
+        } else if guard.settings.audio.pre_roll {
+            Some("Whispr — Microphone warm")
         } else {
             Some("Whispr")
         };
@@ -182,6 +188,7 @@ fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     let id = event.id().as_ref();
     match id {
         MENU_TOGGLE_ID => cli::handle_action(app, cli::CliAction::Toggle),
+        MENU_PAUSE_ID => cli::handle_action(app, cli::CliAction::TogglePause),
         MENU_PASTE_ID => cli::handle_action(app, cli::CliAction::PasteLast),
         MENU_OPEN_ID => cli::handle_action(app, cli::CliAction::Show),
         MENU_SETTINGS_ID => cli::handle_action(app, cli::CliAction::ShowSettings),
@@ -229,6 +236,7 @@ fn build_menu(app: &AppHandle, state: &AppState) -> Result<Menu<tauri::Wry>, Str
     let record_accel = normalize_accelerator(&state.settings.hotkeys.record_toggle);
     let paste_accel = normalize_accelerator(&state.settings.hotkeys.paste_last);
     let open_accel = normalize_accelerator(&state.settings.hotkeys.open_app);
+    let pause_accel = normalize_accelerator(&state.settings.hotkeys.pause_toggle);
 
     let toggle_text = if state.recording {
         "Stop Recording"
@@ -244,6 +252,20 @@ fn build_menu(app: &AppHandle, state: &AppState) -> Result<Menu<tauri::Wry>, Str
     )
     .map_err(|err| err.to_string())?;
 
+    let pause_text = if state.recording_paused {
+        "Resume Recording"
+    } else {
+        "Pause Recording"
+    };
+    let pause_item = MenuItem::with_id(
+        app,
+        MENU_PAUSE_ID,
+        pause_text,
+        state.recording,
+        pause_accel.as_deref(),
+    )
+    .map_err(|err| err.to_string())?;
+
     let paste_item = MenuItem::with_id(
         app,
         MENU_PASTE_ID,
@@ -256,6 +278,7 @@ fn build_menu(app: &AppHandle, state: &AppState) -> Result<Menu<tauri::Wry>, Str
     let recents = build_recents_submenu(app, state).map_err(|err| err.to_string())?;
 
     menu.append(&toggle_item).map_err(|err| err.to_string())?;
+    menu.append(&pause_item).map_err(|err| err.to_string())?;
     menu.append(&paste_item).map_err(|err| err.to_string())?;
     menu.append(&recents).map_err(|err| err.to_string())?;
     menu.append(&PredefinedMenuItem::separator(app).map_err(|err| err.to_string())?)
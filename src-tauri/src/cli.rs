@@ -3,11 +3,18 @@ use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::commands;
+use crate::overlay;
 use crate::state::AppState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CliAction {
     Toggle,
+    ToggleTranslate,
+    Start,
+    Stop,
+    Pause,
+    Resume,
+    TogglePause,
     PasteLast,
     Show,
     ShowSettings,
@@ -22,7 +29,26 @@ pub fn handle_action(app: &AppHandle, action: CliAction) {
     match action {
         CliAction::Toggle => {
             let state = app.state::<Mutex<AppState>>();
-            let _ = commands::toggle_recording_with_state_and_emit(app, state.inner());
+            let _ = commands::toggle_recording_with_state_and_emit(app, state.inner(), None);
+        }
+        CliAction::ToggleTranslate => {
+            let state = app.state::<Mutex<AppState>>();
+            let _ = commands::toggle_recording_with_state_and_emit(app, state.inner(), Some(true));
+        }
+        CliAction::Start | CliAction::Stop => {
+            let state = app.state::<Mutex<AppState>>();
+            let recording = action == CliAction::Start;
+            let _ =
+                commands::set_recording_with_state_and_emit(app, state.inner(), recording, None);
+        }
+        CliAction::Pause | CliAction::Resume | CliAction::TogglePause => {
+            let paused = match action {
+                CliAction::Pause => Some(true),
+                CliAction::Resume => Some(false),
+                _ => None,
+            };
+            let state = app.state::<Mutex<AppState>>();
+            let _ = commands::set_recording_paused_with_state_and_emit(app, state.inner(), paused);
         }
         CliAction::PasteLast => {
             let state = app.state::<Mutex<AppState>>();
@@ -42,6 +68,8 @@ pub fn handle_action(app: &AppHandle, action: CliAction) {
             let _ = app.emit("open-settings", true);
         }
         CliAction::Quit => {
+            // Don't leave the overlay showing a warm microphone after exit.
+            let _ = overlay::set_mic_warm(false);
             app.exit(0);
         }
     }
@@ -50,6 +78,14 @@ pub fn handle_action(app: &AppHandle, action: CliAction) {
 fn action_from_arg(arg: &str) -> Option<CliAction> {
     match arg {
         "--toggle" | "toggle" => Some(CliAction::Toggle),
+        "--toggle-translate" | "toggle-translate" | "--translate" | "translate" => {
+            Some(CliAction::ToggleTranslate)
+        }
+        "--start" | "start" => Some(CliAction::Start),
+        "--stop" | "stop" => Some(CliAction::Stop),
+        "--pause" | "pause" => Some(CliAction::Pause),
+        "--resume" | "resume" => Some(CliAction::Resume),
+        "--toggle-pause" | "toggle-pause" => Some(CliAction::TogglePause),
         "--paste-last" | "paste-last" | "--paste_last" | "paste_last" => Some(CliAction::PasteLast),
         "--show" | "show" | "--open" | "open" | "--focus" | "focus" => Some(CliAction::Show),
         "--show-settings" | "show-settings" | "--settings" | "settings" => {
@@ -70,6 +106,30 @@ mod tests {
         assert_eq!(parse_cli_action(&args), Some(CliAction::Toggle));
     }
 
+    #[test]
+    fn parse_cli_action_toggle_translate() {
+        let args = vec!["whispr".to_string(), "--toggle-translate".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::ToggleTranslate));
+    }
+
+    #[test]
+    fn parse_cli_action_start_and_stop() {
+        let args = vec!["whispr".to_string(), "--start".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::Start));
+        let args = vec!["whispr".to_string(), "--stop".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::Stop));
+    }
+
+    #[test]
+    fn parse_cli_action_pause_and_resume() {
+        let args = vec!["whispr".to_string(), "--pause".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::Pause));
+        let args = vec!["whispr".to_string(), "resume".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::Resume));
+        let args = vec!["whispr".to_string(), "--toggle-pause".to_string()];
+        assert_eq!(parse_cli_action(&args), Some(CliAction::TogglePause));
+    }
+
     #[test]
     fn parse_cli_action_paste_last() {
         let args = vec!["whispr".to_string(), "--paste-last".to_string()];
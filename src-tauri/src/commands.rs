@@ -11,35 +11,83 @@ use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
 use crate::app_tray;
-use crate::core::audio::AudioDevice;
+use crate::core::audio::{AudioDevice, AutoStopReason, CapturedAudio};
+use crate::core::evaluation::ErrorCounts;
+use crate::core::streaming::StreamingTranscript;
 use crate::core::{
-    audio, audio_import, automation, autostart, embedding, macos_permissions, models, runtime,
-    storage, summary, transcription,
+    audio, audio_import, automation, autostart, benchmark, dictation, embedding, evaluation,
+    macos_permissions, models, replacements, runtime, storage, streaming, summary, transcription,
 };
+use crate::jobs;
 use crate::overlay;
-use crate::settings::Settings;
+use crate::settings::{
+    AutomationSettings, Settings, DECODING_PRESET_ACCURATE, DECODING_PRESET_FAST,
+    TRANSCRIPTION_BACKEND_HTTP,
+};
 use crate::state::AppState;
 use crate::tray;
 use crate::types::{
-    BenchmarkResult, Clip, ImportFailure, ImportResult, MacosPermissions, ModelInfo,
-    PerformanceInfo, RuntimeInfo, StorageStats, ToggleResult, Transcript, UpdateInfo,
+    BenchmarkReport, BenchmarkResult, Clip, ConfidenceSpan, DictationCommand, EvaluationFile,
+    EvaluationResult, ImportFailure, ImportResult, MacosPermissions, ModelInfo, PerformanceInfo,
+    ReplacementRule, RuntimeInfo, StorageStats, ToggleResult, Transcript, TranscriptionJob,
+    TranscriptionTask, UpdateInfo,
 };
 struct ToggleOutcome {
     result: ToggleResult,
     started_at_ms: Option<i64>,
-    focus_window_id: Option<String>,
-    automation_settings: Option<crate::settings::AutomationSettings>,
+}
+
+/// Cancel flag of one file transcription, registered in `AppState::transcription_cancels` for
+/// as long as it lives.
+struct TranscriptionCancel<'a> {
+    state: &'a Mutex<AppState>,
+    id: String,
+    flag: Arc<AtomicBool>,
+}
+
+impl<'a> TranscriptionCancel<'a> {
+    fn register(state: &'a Mutex<AppState>, task: TranscriptionTask) -> Self {
+        let id = Uuid::new_v4().to_string();
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Ok(mut guard) = state.lock() {
+            guard
+                .transcription_cancels
+                .insert(id.clone(), (task, flag.clone()));
+        }
+        Self { state, id, flag }
+    }
+}
+
+impl std::ops::Deref for TranscriptionCancel<'_> {
+    type Target = AtomicBool;
+
+    fn deref(&self) -> &AtomicBool {
+        &self.flag
+    }
+}
+
+impl Drop for TranscriptionCancel<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut guard) = self.state.lock() {
+            guard.transcription_cancels.remove(&self.id);
+        }
+    }
 }
 
 #[derive(Clone, Serialize)]
 struct RecordingEvent {
     recording: bool,
+    paused: bool,
+    /// Moved forward by the time spent paused, so time since it is time recorded.
     started_at_ms: Option<i64>,
 }
 
 #[derive(Clone, Serialize)]
 struct PreviewEvent {
+    /// `committed` and `tentative` joined, for consumers that don't style them apart.
     text: String,
+    committed: String,
+    tentative: String,
 }
 
 fn emit_transcription_started(app: &AppHandle) {
@@ -53,6 +101,7 @@ pub struct TranscriptUpdate {
     title: Option<String>,
     summary: Option<String>,
     tags: Option<Vec<String>>,
+    speaker_names: Option<Vec<String>>,
 }
 
 #[derive(Clone, Serialize)]
@@ -69,6 +118,37 @@ struct ImportProgress {
     path: String,
 }
 
+#[derive(Clone, Serialize)]
+struct TranscriptionProgress {
+    path: String,
+    percent: f32,
+}
+
+#[derive(Clone, Serialize)]
+struct RecordingLimitEvent {
+    /// 0 once the oldest audio is being dropped.
+    remaining_seconds: u32,
+    limit_seconds: u32,
+}
+
+#[derive(Clone, Serialize)]
+struct BenchmarkProgress {
+    index: usize,
+    total: usize,
+    model: String,
+    threads: u32,
+    gpu: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct EvaluationProgress {
+    index: usize,
+    total: usize,
+    model: String,
+    preset: String,
+    path: String,
+}
+
 #[derive(Clone, Serialize)]
 struct AutomationErrorEvent {
     message: String,
@@ -81,23 +161,36 @@ const PREVIEW_INTERVAL_GPU_MS: u64 = 4500;
 const PREVIEW_INTERVAL_MIN_MS: u64 = 3000;
 const PREVIEW_INTERVAL_MAX_MS: u64 = 12000;
 const PREVIEW_BACKLOG_SECONDS: f32 = 12.0;
+/// Warn this long before a recording fills the in-memory buffer.
+const RECORDING_LIMIT_WARNING_SECONDS: u32 = 60;
+const AUTO_STOP_POLL_MS: u64 = 200;
 
 fn emit_recording_event(app: &AppHandle, outcome: &ToggleOutcome) {
     let payload = RecordingEvent {
         recording: outcome.result.recording,
+        paused: false,
         started_at_ms: outcome.started_at_ms,
     };
     let _ = app.emit("recording-state", payload);
 }
 
-fn emit_transcript_event(app: &AppHandle, transcript: &Option<Transcript>) {
+pub fn emit_transcript_event(app: &AppHandle, transcript: &Option<Transcript>) {
     if let Some(transcript) = transcript {
         let _ = app.emit("transcript-created", transcript);
     }
 }
 
-fn emit_preview_event(app: &AppHandle, text: String) {
-    let payload = PreviewEvent { text };
+fn emit_preview_event(app: &AppHandle, committed: String, tentative: String) {
+    let text = match (committed.is_empty(), tentative.is_empty()) {
+        (_, true) => committed.clone(),
+        (true, false) => tentative.clone(),
+        (false, false) => format!("{committed} {tentative}"),
+    };
+    let payload = PreviewEvent {
+        text,
+        committed,
+        tentative,
+    };
     let _ = app.emit("transcript-preview", payload);
 }
 
@@ -109,6 +202,79 @@ fn stop_preview_thread(state: &Mutex<AppState>) {
     }
 }
 
+/// Warn before a recording outgrows the in-memory buffer and starts losing its beginning. Spooled
+/// recordings have no limit.
+fn start_limit_watch(app: AppHandle, state: &Mutex<AppState>) {
+    let (audio_tx, started_at_ms) = match state.lock() {
+        Ok(guard) if !guard.settings.audio.long_recording => {
+            (guard.audio_tx.clone(), guard.recording_started_at_ms)
+        }
+        _ => return,
+    };
+    std::thread::spawn(move || {
+        let warn_at = audio::MAX_RECORDING_SECONDS - RECORDING_LIMIT_WARNING_SECONDS;
+        let mut warned = false;
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+            let current = app
+                .state::<Mutex<AppState>>()
+                .lock()
+                .map(|guard| guard.recording_started_at_ms)
+                .unwrap_or(None);
+            if current.is_none() || current != started_at_ms {
+                break;
+            }
+            let Ok(stats) = audio::stats(&audio_tx) else {
+                break;
+            };
+            let seconds = stats.recorded_seconds();
+            let limit_reached = seconds >= audio::MAX_RECORDING_SECONDS;
+            if (seconds >= warn_at && !warned) || limit_reached {
+                let _ = app.emit(
+                    "recording-limit",
+                    RecordingLimitEvent {
+                        remaining_seconds: audio::MAX_RECORDING_SECONDS.saturating_sub(seconds),
+                        limit_seconds: audio::MAX_RECORDING_SECONDS,
+                    },
+                );
+                warned = true;
+            }
+            if limit_reached {
+                break;
+            }
+        }
+    });
+}
+
+/// Stop an auto-stop recording once the input callback has decided the speaker is done, the same
+/// way the record hotkey would. A recording nobody spoke in is discarded by the stop itself.
+fn start_auto_stop_watch(app: AppHandle, state: &Mutex<AppState>) {
+    let (audio_tx, started_at_ms) = match state.lock() {
+        Ok(guard) if guard.settings.audio.auto_stop => {
+            (guard.audio_tx.clone(), guard.recording_started_at_ms)
+        }
+        _ => return,
+    };
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(AUTO_STOP_POLL_MS));
+        let state = app.state::<Mutex<AppState>>();
+        let current = state
+            .lock()
+            .map(|guard| guard.recording_started_at_ms)
+            .unwrap_or(None);
+        if current.is_none() || current != started_at_ms {
+            break;
+        }
+        let Ok(stats) = audio::stats(&audio_tx) else {
+            break;
+        };
+        if stats.auto_stop.is_some() {
+            let _ = set_recording_with_state_and_emit(&app, state.inner(), false, None);
+            break;
+        }
+    });
+}
+
 fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
     let (audio_tx, settings, cancel, ui_active) = {
         let mut guard = match state.lock() {
@@ -120,9 +286,14 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
         }
         let cancel = Arc::new(AtomicBool::new(false));
         guard.preview_cancel = Some(cancel.clone());
+        guard.preview_prefix = None;
+        let mut settings = guard.settings.clone();
+        if let Some(translate) = guard.recording_translate {
+            settings.transcription.translate = translate;
+        }
         (
             guard.audio_tx.clone(),
-            guard.settings.clone(),
+            settings,
             cancel,
             guard.ui_active.clone(),
         )
@@ -133,8 +304,8 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
         // pay model load / backend init costs (especially noticeable with Metal).
         let _ = transcription::ensure_context(&settings);
 
-        let mut cursor = 0_usize;
-        let mut preview = String::new();
+        let mut stream = StreamingTranscript::default();
+        let mut last_total = 0_usize;
         let wants_gpu = settings.transcription.use_gpu && cfg!(feature = "_gpu");
         let mut interval_ms = if wants_gpu {
             PREVIEW_INTERVAL_GPU_MS
@@ -147,35 +318,36 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
                 break;
             }
 
+            let stats = match audio::stats(&audio_tx) {
+                Ok(stats) => stats,
+                Err(_) => break,
+            };
+            let frame_len = stats.channels.max(1) as usize;
+            let rate = stats.sample_rate.max(1) as i64;
+            let to_ms = |samples: usize| (samples / frame_len) as i64 * 1000 / rate;
+
             if !ui_active.load(Ordering::Relaxed) {
                 // UI isn't visible/focused; avoid expensive snapshot+inference.
-                // Keep cursor near the tail so we won't allocate huge snapshots if UI becomes
+                // Keep the window near the tail so we won't allocate huge snapshots if UI becomes
                 // active mid-recording.
-                if let Ok(stats) = audio::stats(&audio_tx) {
-                    let keep = ((stats.sample_rate as f32)
-                        * (stats.channels as f32).max(1.0)
-                        * PREVIEW_BACKLOG_SECONDS)
-                        .round()
-                        .max(0.0) as usize;
-                    cursor = stats.total_samples.saturating_sub(keep);
-                }
+                let backlog_ms = (PREVIEW_BACKLOG_SECONDS * 1000.0) as i64;
+                stream.skip_to(to_ms(stats.total_samples) - backlog_ms);
                 std::thread::sleep(Duration::from_millis(PREVIEW_INACTIVE_POLL_MS));
                 continue;
             }
 
-            let snapshot = match audio::snapshot_audio(&audio_tx, cursor) {
+            let from = (stream.window_start_ms() * rate / 1000) as usize * frame_len;
+            let snapshot = match audio::snapshot_audio(&audio_tx, from) {
                 Ok(snapshot) => snapshot,
                 Err(_) => break,
             };
 
-            if snapshot.samples.is_empty() {
+            if snapshot.samples.is_empty() || snapshot.total_samples == last_total {
                 interval_ms = (interval_ms + 500).min(PREVIEW_INTERVAL_MAX_MS);
                 std::thread::sleep(Duration::from_millis(interval_ms));
                 continue;
             }
 
-            cursor = snapshot.total_samples;
-
             let seconds = snapshot.samples.len() as f32
                 / (snapshot.sample_rate as f32 * snapshot.channels as f32).max(1.0);
             if seconds < PREVIEW_MIN_SECONDS {
@@ -184,6 +356,14 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
                 continue;
             }
 
+            last_total = snapshot.total_samples;
+            // The ring buffer may already have dropped audio before `from`.
+            let window_start_ms = to_ms(snapshot.total_samples - snapshot.samples.len());
+            let window_end_ms = to_ms(snapshot.total_samples);
+            if window_start_ms > stream.window_start_ms() {
+                stream.skip_to(window_start_ms + streaming::WINDOW_OVERLAP_MS);
+            }
+
             let audio = audio::RecordedAudio {
                 samples: snapshot.samples,
                 sample_rate: snapshot.sample_rate,
@@ -196,18 +376,21 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
 
             let started = Instant::now();
             match transcription::transcribe_preview(&settings, audio) {
-                Ok(chunk) => {
-                    let chunk = chunk.trim();
-                    if !chunk.is_empty() {
-                        preview = merge_preview_text(&preview, chunk);
-                        emit_preview_event(&app, preview.clone());
+                Ok(output) => {
+                    stream.update(window_start_ms, window_end_ms, output);
+                    if let Ok(mut guard) = app.state::<Mutex<AppState>>().lock() {
+                        // Checked under the lock so a stopped recording can't receive a prefix
+                        // from a decode that finished after it was taken.
+                        if !cancel.load(Ordering::Relaxed) {
+                            guard.preview_prefix = stream.prefix();
+                        }
                     }
+                    emit_preview_event(&app, stream.committed_text(), stream.tentative_text());
                 }
                 Err(err) => {
                     // Non-fatal: preview transcription is best-effort, but log failures for debugging.
                     eprintln!(
-                        "[DEBUG] preview transcription failed: {err} (cursor={cursor}, preview_len={}, model={}, use_gpu={})",
-                        preview.len(),
+                        "[DEBUG] preview transcription failed: {err} (window_start_ms={window_start_ms}, model={}, use_gpu={})",
                         settings.transcription.model,
                         settings.transcription.use_gpu
                     );
@@ -229,41 +412,6 @@ fn start_preview_thread(app: AppHandle, state: &Mutex<AppState>) {
     });
 }
 
-fn merge_preview_text(current: &str, incoming: &str) -> String {
-    if current.is_empty() {
-        return incoming.to_string();
-    }
-    if incoming.is_empty() {
-        return current.to_string();
-    }
-
-    let current_lower = current.to_lowercase();
-    let incoming_lower = incoming.to_lowercase();
-    let incoming_chars: Vec<char> = incoming.chars().collect();
-    let incoming_lower_chars: Vec<char> = incoming_lower.chars().collect();
-    let incoming_len = incoming_chars.len().min(incoming_lower_chars.len());
-    let max_overlap = current_lower.chars().count().min(incoming_len).min(48);
-    let mut overlap = 0;
-
-    for i in 1..=max_overlap {
-        let candidate: String = incoming_lower_chars[..i].iter().collect();
-        if current_lower.ends_with(&candidate) {
-            overlap = i;
-        }
-    }
-
-    if overlap > 0 {
-        let suffix: String = incoming_chars[overlap..].iter().collect();
-        let trimmed = suffix.trim_start();
-        if trimmed.is_empty() {
-            return current.to_string();
-        }
-        return format!("{current} {trimmed}");
-    }
-
-    format!("{current} {incoming}")
-}
-
 fn normalize_optional(value: String) -> Option<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -324,26 +472,36 @@ pub fn set_audio_input_device(
         storage::save_settings(&guard.settings)?;
         guard.settings.clone()
     };
+    sync_warm_capture(state.inner(), &settings);
 
     let _ = app.emit("settings-updated", settings.clone());
     Ok(settings)
 }
 
+/// Open or close the warm microphone to match the audio settings.
+fn sync_warm_capture(state: &Mutex<AppState>, settings: &Settings) {
+    let Ok(audio_tx) = state.lock().map(|guard| guard.audio_tx.clone()) else {
+        return;
+    };
+    audio::update_warm_capture(&audio_tx, &settings.audio);
+}
+
 #[tauri::command]
 pub fn save_settings(
     app: AppHandle,
     state: State<'_, Mutex<AppState>>,
-    settings: Settings,
+    mut settings: Settings,
 ) -> Result<Settings, String> {
+    settings.transcription.apply_decoding_preset();
     let previous_settings = state
         .lock()
         .map(|guard| guard.settings.clone())
         .map_err(|_| "state lock poisoned".to_string())?;
     let launch_changed = previous_settings.app.launch_on_login != settings.app.launch_on_login;
-    let transcription_context_changed = previous_settings.transcription.model
-        != settings.transcription.model
-        || previous_settings.transcription.model_dir != settings.transcription.model_dir
-        || previous_settings.transcription.use_gpu != settings.transcription.use_gpu;
+    // Contexts are cached per model file, so only a GPU toggle needs a fresh load (to retry GPU
+    // init after a fallback).
+    let transcription_context_changed =
+        previous_settings.transcription.use_gpu != settings.transcription.use_gpu;
 
     if !audio::input_device_available(&settings.audio.input_device_id) {
         return Err(format!(
@@ -352,6 +510,13 @@ pub fn save_settings(
         ));
     }
 
+    if settings.transcription.backend == TRANSCRIPTION_BACKEND_HTTP {
+        // Warming the HTTP backend only validates the server URL.
+        transcription::ensure_context(&settings)?;
+    } else if settings.transcription.translate {
+        models::ensure_supports_translation(&settings.transcription.model)?;
+    }
+
     if launch_changed {
         autostart::apply_launch_on_login(settings.app.launch_on_login)
             .map_err(|err| format!("Failed to update launch on login: {err}"))?;
@@ -383,6 +548,9 @@ pub fn save_settings(
             transcription::clear_last_gpu_error();
         }
     }
+    if previous_settings.audio != settings.audio {
+        sync_warm_capture(state.inner(), &settings);
+    }
 
     app_tray::refresh_tray(&app, state.inner());
 
@@ -419,14 +587,30 @@ pub fn search_transcripts(
     state: State<'_, Mutex<AppState>>,
     query: String,
     limit: Option<usize>,
+    language: Option<String>,
 ) -> Result<Vec<Transcript>, String> {
     let query = query.trim().to_string();
+    let language = language
+        .map(|value| value.trim().to_lowercase())
+        .filter(|value| !value.is_empty());
+    let matches_language = |transcript: &Transcript| match language.as_deref() {
+        Some(language) => transcript
+            .language
+            .as_deref()
+            .is_some_and(|value| value.eq_ignore_ascii_case(language)),
+        None => true,
+    };
 
     if query.is_empty() {
         let guard = state
             .lock()
             .map_err(|_| "state lock poisoned".to_string())?;
-        let mut results = guard.transcripts.clone();
+        let mut results = guard
+            .transcripts
+            .iter()
+            .filter(|transcript| matches_language(transcript))
+            .cloned()
+            .collect::<Vec<_>>();
         if let Some(limit) = limit {
             results.truncate(limit);
         }
@@ -484,6 +668,9 @@ pub fn search_transcripts(
 
     let mut scored: Vec<(f32, Transcript)> = Vec::with_capacity(transcripts_snapshot.len());
     for transcript in transcripts_snapshot.into_iter() {
+        if !matches_language(&transcript) {
+            continue;
+        }
         let embedding = transcript
             .embedding
             .clone()
@@ -521,6 +708,27 @@ pub fn search_transcripts(
     Ok(results)
 }
 
+#[tauri::command]
+pub fn get_low_confidence_spans(
+    state: State<'_, Mutex<AppState>>,
+    id: String,
+    threshold: Option<f32>,
+) -> Result<Vec<ConfidenceSpan>, String> {
+    let guard = state
+        .lock()
+        .map_err(|_| "state lock poisoned".to_string())?;
+    let transcript = guard
+        .transcripts
+        .iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| "Transcript not found".to_string())?;
+    let threshold = threshold.unwrap_or(transcription::DEFAULT_LOW_CONFIDENCE_THRESHOLD);
+    Ok(transcription::low_confidence_spans(
+        &transcript.words,
+        threshold,
+    ))
+}
+
 #[tauri::command]
 pub fn list_clips(state: State<'_, Mutex<AppState>>) -> Result<Vec<Clip>, String> {
     let guard = state
@@ -571,6 +779,126 @@ pub fn delete_clip(state: State<'_, Mutex<AppState>>, id: String) -> Result<bool
     Ok(true)
 }
 
+#[tauri::command]
+pub fn list_dictation_commands(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<DictationCommand>, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::load_dictation_commands(&settings, None)
+}
+
+/// Create (empty `id`) or update a dictation command.
+#[tauri::command]
+pub fn save_dictation_command(
+    state: State<'_, Mutex<AppState>>,
+    command: DictationCommand,
+) -> Result<DictationCommand, String> {
+    let phrase = command
+        .phrase
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let language = command.language.trim().to_ascii_lowercase();
+    if phrase.is_empty() || language.is_empty() {
+        return Err("Dictation commands need a phrase and a language".to_string());
+    }
+    if !dictation::ACTIONS.contains(&command.action.as_str()) {
+        return Err(format!("Unknown dictation action: {}", command.action));
+    }
+    let needs_value = matches!(
+        command.action.as_str(),
+        dictation::ACTION_INSERT | dictation::ACTION_OPEN
+    );
+    if needs_value && command.value.is_empty() {
+        return Err("This dictation command needs text to insert".to_string());
+    }
+
+    let command = DictationCommand {
+        id: if command.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            command.id
+        },
+        language,
+        phrase: phrase.to_lowercase(),
+        action: command.action,
+        value: if needs_value {
+            command.value
+        } else {
+            String::new()
+        },
+    };
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::upsert_dictation_command(&settings, &command)?;
+    Ok(command)
+}
+
+#[tauri::command]
+pub fn delete_dictation_command(
+    state: State<'_, Mutex<AppState>>,
+    id: String,
+) -> Result<bool, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::delete_dictation_command(&settings, &id)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn list_replacement_rules(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<ReplacementRule>, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::load_replacement_rules(&settings)
+}
+
+/// Create (empty `id`) or update a replacement rule. Regex patterns are checked before saving.
+#[tauri::command]
+pub fn save_replacement_rule(
+    state: State<'_, Mutex<AppState>>,
+    rule: ReplacementRule,
+) -> Result<ReplacementRule, String> {
+    replacements::compile(&rule)?;
+    let rule = ReplacementRule {
+        id: if rule.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            rule.id
+        },
+        ..rule
+    };
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::upsert_replacement_rule(&settings, &rule)?;
+    Ok(rule)
+}
+
+#[tauri::command]
+pub fn delete_replacement_rule(
+    state: State<'_, Mutex<AppState>>,
+    id: String,
+) -> Result<bool, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::delete_replacement_rule(&settings, &id)?;
+    Ok(true)
+}
+
 #[tauri::command]
 pub fn update_transcript(
     app: AppHandle,
@@ -593,6 +921,11 @@ pub fn update_transcript(
             if trimmed.is_empty() {
                 return Err("Transcript text cannot be empty".to_string());
             }
+            if transcript.text != trimmed {
+                // Timings, confidences and speaker turns describe the old text.
+                transcript.segments.clear();
+                transcript.words.clear();
+            }
             transcript.text = trimmed.to_string();
             transcript.title = summary::generate_title(trimmed);
             transcript.summary = summary::generate_summary(trimmed);
@@ -607,6 +940,12 @@ pub fn update_transcript(
         if let Some(tags) = update.tags {
             transcript.tags = tags;
         }
+        if let Some(names) = update.speaker_names {
+            transcript.speaker_names = names
+                .into_iter()
+                .map(|name| name.trim().to_string())
+                .collect();
+        }
 
         let cloned = transcript.clone();
         storage::upsert_transcript(&guard.settings, &cloned)?;
@@ -679,7 +1018,8 @@ pub fn clear_transcripts(
     Ok(true)
 }
 
-#[tauri::command]
+// Runs off the main thread so `cancel_transcription` can be handled while it works.
+#[tauri::command(async)]
 pub fn import_audio_files(
     app: AppHandle,
     state: State<'_, Mutex<AppState>>,
@@ -696,6 +1036,8 @@ pub fn import_audio_files(
         .lock()
         .map(|guard| guard.settings.clone())
         .map_err(|_| "state lock poisoned".to_string())?;
+    let cancel = TranscriptionCancel::register(state.inner(), TranscriptionTask::Import);
+    let rules = storage::load_replacement_rules(&settings).unwrap_or_default();
 
     let total = paths.len();
     let mut imported = Vec::new();
@@ -722,8 +1064,23 @@ pub fn import_audio_files(
             }
         };
 
-        let text = match transcription::transcribe(&settings, decoded.audio) {
-            Ok(text) => text,
+        let output = match transcription::transcribe_chunked(
+            &settings,
+            decoded.audio,
+            "",
+            &cancel,
+            |percent| {
+                let _ = app.emit(
+                    "transcription-progress",
+                    TranscriptionProgress {
+                        path: path.clone(),
+                        percent,
+                    },
+                );
+            },
+        ) {
+            Ok(output) => output,
+            Err(_) if cancel.load(Ordering::Relaxed) => break,
             Err(err) => {
                 failures.push(ImportFailure {
                     path: path.clone(),
@@ -737,6 +1094,7 @@ pub fn import_audio_files(
             .duration_since(UNIX_EPOCH)
             .map(|duration| duration.as_millis() as i64)
             .unwrap_or(0);
+        let (text, vocab_suggestions, applied) = jobs::finish_text(&settings, &rules, &output);
         let title = summary::generate_title(&text);
         let summary = summary::generate_summary(&text);
         let embedding = embedding::embed_text(&text);
@@ -750,6 +1108,15 @@ pub fn import_audio_files(
             summary,
             tags: Vec::new(),
             audio_path: None,
+            segments: output.segments,
+            words: output.words,
+            language: output.language,
+            language_probabilities: output.language_probabilities,
+            translated: output.translated,
+            filtered: output.filtered,
+            speaker_names: Vec::new(),
+            replacements: applied,
+            vocab_suggestions,
             embedding: Some(embedding),
         };
 
@@ -775,6 +1142,167 @@ pub fn import_audio_files(
     })
 }
 
+/// Transcribe audio files that have a reference transcript next to them with every installed
+/// model and decoding preset, and store how far each configuration is from the references.
+#[tauri::command(async)]
+pub fn evaluate_transcription(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    paths: Vec<String>,
+) -> Result<Vec<EvaluationResult>, String> {
+    let pairs = evaluation::reference_pairs(&paths)?;
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    let rules = storage::load_replacement_rules(&settings).unwrap_or_default();
+
+    let mut samples = Vec::new();
+    for (audio_path, reference_path) in &pairs {
+        let decoded = audio_import::decode_audio_file(audio_path)
+            .map_err(|err| format!("{}: {err}", evaluation::file_name(audio_path)))?;
+        let reference = fs::read_to_string(reference_path).map_err(|err| err.to_string())?;
+        samples.push((audio_path, decoded, reference));
+    }
+
+    // A remote backend has one model of its own; only local models can be compared.
+    let models = if settings.transcription.backend == TRANSCRIPTION_BACKEND_HTTP {
+        vec![settings.transcription.model.clone()]
+    } else {
+        models::list_models(&settings)
+            .into_iter()
+            .filter(|model| model.installed)
+            .map(|model| model.id)
+            .collect()
+    };
+    if models.is_empty() {
+        return Err("Download a model before running an evaluation".to_string());
+    }
+    let configs: Vec<(String, &str)> = models
+        .iter()
+        .flat_map(|model| {
+            [DECODING_PRESET_FAST, DECODING_PRESET_ACCURATE].map(|preset| (model.clone(), preset))
+        })
+        .collect();
+
+    let cancel = TranscriptionCancel::register(state.inner(), TranscriptionTask::Evaluation);
+    let run_id = Uuid::new_v4().to_string();
+    let total = configs.len() * samples.len();
+    let mut results = Vec::new();
+    let outcome = (|| {
+        for (config_index, (model, preset)) in configs.iter().enumerate() {
+            let mut config = settings.clone();
+            config.transcription.model = model.clone();
+            config.transcription.decoding_preset = preset.to_string();
+            config.transcription.apply_decoding_preset();
+
+            let mut words = ErrorCounts::default();
+            let mut chars = ErrorCounts::default();
+            let mut audio_ms = 0_u64;
+            let mut duration_ms = 0;
+            let mut files = Vec::new();
+            for (file_index, (audio_path, decoded, reference)) in samples.iter().enumerate() {
+                let _ = app.emit(
+                    "evaluation-progress",
+                    EvaluationProgress {
+                        index: config_index * samples.len() + file_index + 1,
+                        total,
+                        model: model.clone(),
+                        preset: preset.to_string(),
+                        path: audio_path.to_string_lossy().to_string(),
+                    },
+                );
+                let started = Instant::now();
+                let output = transcription::transcribe_chunked(
+                    &config,
+                    decoded.audio.clone(),
+                    "",
+                    &cancel,
+                    |_| {},
+                )?;
+                duration_ms += started.elapsed().as_millis() as u64;
+                audio_ms += u64::from(decoded.duration_ms);
+
+                let (hypothesis, _, _) = jobs::finish_text(&config, &rules, &output);
+                let file_words = evaluation::word_errors(reference, &hypothesis);
+                let file_chars = evaluation::char_errors(reference, &hypothesis);
+                words.add(file_words);
+                chars.add(file_chars);
+                files.push(EvaluationFile {
+                    name: evaluation::file_name(audio_path),
+                    wer: file_words.rate(),
+                    cer: file_chars.rate(),
+                    hypothesis,
+                });
+            }
+
+            let audio_seconds = audio_ms as f32 / 1000.0;
+            let result = EvaluationResult {
+                id: Uuid::new_v4().to_string(),
+                run_id: run_id.clone(),
+                created_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_millis() as i64)
+                    .unwrap_or(0),
+                model: model.clone(),
+                preset: preset.to_string(),
+                custom_vocab: settings.transcription.custom_vocab.clone(),
+                wer: words.rate(),
+                cer: chars.rate(),
+                substitutions: words.substitutions,
+                deletions: words.deletions,
+                insertions: words.insertions,
+                reference_words: words.reference,
+                audio_seconds,
+                duration_ms,
+                realtime_factor: audio_seconds / (duration_ms as f32 / 1000.0).max(0.001),
+                files,
+            };
+            storage::insert_evaluation(&settings, &result)?;
+            results.push(result);
+        }
+        Ok::<_, String>(())
+    })();
+
+    // Configurations finished before a cancel are still worth showing.
+    match outcome {
+        Err(_) if cancel.load(Ordering::Relaxed) => Ok(results),
+        Err(err) => Err(err),
+        Ok(()) => Ok(results),
+    }
+}
+
+#[tauri::command]
+pub fn list_evaluations(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<EvaluationResult>, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::load_evaluations(&settings)
+}
+
+/// Stop the running file transcriptions of `task`, or all of them. Files already imported are
+/// kept. Returns whether anything was running.
+#[tauri::command]
+pub fn cancel_transcription(
+    state: State<'_, Mutex<AppState>>,
+    task: Option<TranscriptionTask>,
+) -> Result<bool, String> {
+    let guard = state
+        .lock()
+        .map_err(|_| "state lock poisoned".to_string())?;
+    let mut cancelled = false;
+    for (running, cancel) in guard.transcription_cancels.values() {
+        if task.is_none_or(|task| task == *running) {
+            cancel.store(true, Ordering::Relaxed);
+            cancelled = true;
+        }
+    }
+    Ok(cancelled)
+}
+
 #[tauri::command]
 pub fn get_runtime_info(state: State<'_, Mutex<AppState>>) -> RuntimeInfo {
     let settings = state
@@ -842,37 +1370,147 @@ pub fn get_performance_info(state: State<'_, Mutex<AppState>>) -> PerformanceInf
         thread_count,
         gpu_error,
         gpu_name,
+        resident_models: transcription::resident_models(),
     }
 }
 
-#[tauri::command]
+/// Transcribe `path` with every installed model, a range of thread counts, and on CPU and GPU,
+/// storing each combination's speed and recommending one for the configured model.
+#[tauri::command(async)]
 pub fn benchmark_transcription(
+    app: AppHandle,
     state: State<'_, Mutex<AppState>>,
     path: String,
-) -> Result<BenchmarkResult, String> {
+) -> Result<BenchmarkReport, String> {
     let settings = state
         .lock()
         .map(|guard| guard.settings.clone())
         .map_err(|_| "state lock poisoned".to_string())?;
+    if settings.transcription.backend == TRANSCRIPTION_BACKEND_HTTP {
+        return Err("Benchmarks measure local models; switch the backend to whisper".to_string());
+    }
 
     let decoded = audio_import::decode_audio_file(Path::new(&path))?;
     let audio_seconds = decoded.duration_ms as f32 / 1000.0;
 
-    let started = Instant::now();
-    let text = transcription::transcribe(&settings, decoded.audio)?;
-    let duration_ms = started.elapsed().as_millis() as u64;
+    let models: Vec<String> = models::list_models(&settings)
+        .into_iter()
+        .filter(|model| model.installed)
+        .map(|model| model.id)
+        .collect();
+    if models.is_empty() {
+        return Err("Download a model before running a benchmark".to_string());
+    }
+    let cpu_count = std::thread::available_parallelism()
+        .map(|count| count.get() as u32)
+        .unwrap_or(4);
+    let threads = benchmark::thread_counts(cpu_count);
+    let gpu_options: &[bool] = if cfg!(feature = "_gpu") {
+        &[false, true]
+    } else {
+        &[false]
+    };
+    let gpu_name = transcription::detect_gpu_name();
 
-    let duration_seconds = (duration_ms as f32 / 1000.0).max(0.001);
-    let realtime_factor = audio_seconds / duration_seconds;
+    let cancel = TranscriptionCancel::register(state.inner(), TranscriptionTask::Benchmark);
+    let run_id = Uuid::new_v4().to_string();
+    let total = models.len() * gpu_options.len() * threads.len();
+    let mut results = Vec::new();
+    let outcome = (|| {
+        for model in &models {
+            for &use_gpu in gpu_options {
+                let mut config = settings.clone();
+                config.transcription.model = model.clone();
+                config.transcription.use_gpu = use_gpu;
+
+                // Drop every loaded context so the load is timed from disk.
+                transcription::invalidate_context_cache();
+                let started = Instant::now();
+                transcription::ensure_context(&config)?;
+                let cold_load_ms = started.elapsed().as_millis() as u64;
+                if use_gpu && transcription::last_gpu_error().is_some() {
+                    // Fell back to the CPU, which the CPU combinations already cover.
+                    continue;
+                }
 
-    Ok(BenchmarkResult {
-        audio_seconds,
-        duration_ms,
-        realtime_factor,
-        text_length: text.len(),
+                for &thread_count in &threads {
+                    let _ = app.emit(
+                        "benchmark-progress",
+                        BenchmarkProgress {
+                            index: results.len() + 1,
+                            total,
+                            model: model.clone(),
+                            threads: thread_count,
+                            gpu: use_gpu,
+                        },
+                    );
+                    config.transcription.threads = thread_count;
+                    let measures_memory = benchmark::reset_peak_memory();
+                    let started = Instant::now();
+                    transcription::transcribe_chunked(
+                        &config,
+                        decoded.audio.clone(),
+                        "",
+                        &cancel,
+                        |_| {},
+                    )?;
+                    let duration_ms = started.elapsed().as_millis() as u64;
+
+                    let result = BenchmarkResult {
+                        id: Uuid::new_v4().to_string(),
+                        run_id: run_id.clone(),
+                        created_at: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|duration| duration.as_millis() as i64)
+                            .unwrap_or(0),
+                        model: model.clone(),
+                        threads: thread_count,
+                        gpu: use_gpu,
+                        cold_load_ms,
+                        audio_seconds,
+                        duration_ms,
+                        realtime_factor: audio_seconds / (duration_ms as f32 / 1000.0).max(0.001),
+                        peak_memory_bytes: measures_memory
+                            .then(benchmark::peak_memory_bytes)
+                            .flatten(),
+                        gpu_name: gpu_name.clone(),
+                        cpu_count,
+                    };
+                    storage::insert_benchmark(&settings, &result)?;
+                    results.push(result);
+                }
+            }
+        }
+        Ok::<_, String>(())
+    })();
+
+    // Leave the configured model loaded rather than the last one benchmarked.
+    transcription::invalidate_context_cache();
+    if settings.transcription.use_gpu {
+        transcription::clear_last_gpu_error();
+    }
+    match outcome {
+        Err(_) if cancel.load(Ordering::Relaxed) => {}
+        Err(err) => return Err(err),
+        Ok(()) => {}
+    }
+
+    let recommended = benchmark::recommend(&results, &settings.transcription.model).cloned();
+    Ok(BenchmarkReport {
+        results,
+        recommended,
     })
 }
 
+#[tauri::command]
+pub fn list_benchmarks(state: State<'_, Mutex<AppState>>) -> Result<Vec<BenchmarkResult>, String> {
+    let settings = state
+        .lock()
+        .map(|guard| guard.settings.clone())
+        .map_err(|_| "state lock poisoned".to_string())?;
+    storage::load_benchmarks(&settings)
+}
+
 #[tauri::command]
 pub fn copy_text(text: String) -> Result<bool, String> {
     automation::copy_text(&text)?;
@@ -978,15 +1616,29 @@ pub fn list_audio_devices() -> Vec<AudioDevice> {
     audio::list_input_devices()
 }
 
+/// Start or stop the recording. With `target`, nothing happens when the recording is already in
+/// that state; the check shares the lock that flips it, so racing stops can't restart the mic.
 fn toggle_recording_with_state(
     app: &AppHandle,
     state: &Mutex<AppState>,
-) -> Result<ToggleOutcome, String> {
+    target: Option<bool>,
+    translate: Option<bool>,
+) -> Result<Option<ToggleOutcome>, String> {
     let mut guard = state
         .lock()
         .map_err(|_| "state lock poisoned".to_string())?;
+    if target == Some(guard.recording) {
+        return Ok(None);
+    }
 
     if !guard.recording {
+        // Reject an impossible translate request before the mic opens, not after the user spoke.
+        // A remote server decides for itself whether its model can translate.
+        if translate.unwrap_or(guard.settings.transcription.translate)
+            && guard.settings.transcription.backend != TRANSCRIPTION_BACKEND_HTTP
+        {
+            models::ensure_supports_translation(&guard.settings.transcription.model)?;
+        }
         let audio_settings = guard.settings.audio.clone();
         let audio_tx = guard.audio_tx.clone();
         let settings_snapshot = guard.settings.clone();
@@ -996,18 +1648,27 @@ fn toggle_recording_with_state(
             .map(|duration| duration.as_millis() as i64)
             .unwrap_or(0);
         guard.recording = true;
+        guard.recording_paused = false;
         guard.recording_started_at_ms = Some(started_at_ms);
+        guard.recording_translate = translate;
+        guard.preview_prefix = None;
         drop(guard);
 
-        if let Err(err) = audio::start_recording(&audio_tx, audio_settings, started_at_ms) {
+        let spool_path = audio_settings
+            .long_recording
+            .then(|| storage::spool_path(&settings_snapshot, started_at_ms));
+        if let Err(err) =
+            audio::start_recording(&audio_tx, audio_settings, started_at_ms, spool_path)
+        {
             let mut guard = state
                 .lock()
                 .map_err(|_| "state lock poisoned".to_string())?;
             guard.recording = false;
             guard.recording_started_at = None;
             guard.recording_started_at_ms = None;
+            guard.recording_translate = None;
             guard.last_focus_window = None;
-            let _ = overlay::write_state(false, None, Some(0.0));
+            let _ = overlay::write_state(false, false, None, Some(0.0));
             let _ = tray::write_error(&settings_snapshot, &transcripts_snapshot, &err);
             return Err(err);
         }
@@ -1025,20 +1686,25 @@ fn toggle_recording_with_state(
         guard.recording_started_at = Some(std::time::Instant::now());
         guard.recording_started_at_ms = Some(started_at_ms);
         guard.last_focus_window = automation::capture_focus_window();
-        let _ = overlay::write_state(true, Some(started_at_ms), Some(0.0));
-        return Ok(ToggleOutcome {
+        let _ = overlay::write_state(true, false, Some(started_at_ms), Some(0.0));
+        return Ok(Some(ToggleOutcome {
             result: ToggleResult {
                 recording: true,
-                transcript: None,
+                job: None,
             },
             started_at_ms: Some(started_at_ms),
-            focus_window_id: None,
-            automation_settings: None,
-        });
+        }));
     }
 
     guard.recording = false;
-    guard.recording_started_at_ms = None;
+    guard.recording_paused = false;
+    // Paused time is left out of `duration_ms`, so the start can't be worked out from it later.
+    let started_at_ms = guard.recording_started_at_ms.take().unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0)
+    });
     // Stop live preview before running the (potentially expensive) final transcription so we
     // don't run two Whisper inferences concurrently.
     if let Some(cancel) = guard.preview_cancel.take() {
@@ -1049,94 +1715,122 @@ fn toggle_recording_with_state(
         .take()
         .map(|start| start.elapsed().as_millis() as u32)
         .unwrap_or(0);
-    let settings = guard.settings.clone();
+    let mut settings = guard.settings.clone();
+    // In push-to-talk mode a press shorter than the minimum hold is an accidental tap.
+    let tapped =
+        settings.hotkeys.push_to_talk && duration_ms < settings.hotkeys.push_to_talk_min_hold_ms;
+    let preview_translate = guard
+        .recording_translate
+        .unwrap_or(settings.transcription.translate);
+    // An override on the stopping action wins over the one the recording was started with, but
+    // only if the model can honour it; otherwise the recording keeps its own mode.
+    let translate = translate.filter(|&translate| {
+        if !translate || settings.transcription.backend == TRANSCRIPTION_BACKEND_HTTP {
+            return true;
+        }
+        match models::ensure_supports_translation(&settings.transcription.model) {
+            Ok(()) => true,
+            Err(err) => {
+                eprintln!("Ignoring translate override on stop: {err}");
+                false
+            }
+        }
+    });
+    if let Some(translate) = translate.or(guard.recording_translate.take()) {
+        settings.transcription.translate = translate;
+    }
+    // The preview's committed words can only stand in for the final text if they were decoded
+    // in the same mode.
+    let preview_prefix = guard
+        .preview_prefix
+        .take()
+        .filter(|_| preview_translate == settings.transcription.translate);
     let transcripts_snapshot = guard.transcripts.clone();
     let audio_tx = guard.audio_tx.clone();
     let focus_window = guard.last_focus_window.take();
     drop(guard);
 
+    let stats = audio::stats(&audio_tx).ok();
+    let paused_ms = stats.map(|stats| stats.paused_ms).unwrap_or(0);
+    let duration_ms = duration_ms.saturating_sub(paused_ms);
+    // Nobody spoke, so a transcript could only be a hallucination; drop it like a tap.
+    let silent = stats.and_then(|stats| stats.auto_stop) == Some(AutoStopReason::NoSpeech);
     let audio = match audio::stop_recording(&audio_tx) {
         Ok(audio) => audio,
         Err(err) => {
-            let _ = overlay::write_state(false, None, Some(0.0));
+            let _ = overlay::write_state(false, false, None, Some(0.0));
             let _ = tray::write_error(&settings, &transcripts_snapshot, &err);
             return Err(err);
         }
     };
-    let _ = overlay::write_state(false, None, Some(0.0));
+    let _ = overlay::write_state(false, false, None, Some(0.0));
+
+    if tapped || silent {
+        if let CapturedAudio::Spooled(path) = &audio {
+            let _ = std::fs::remove_file(path);
+        }
+        return Ok(Some(ToggleOutcome {
+            result: ToggleResult {
+                recording: false,
+                job: None,
+            },
+            started_at_ms: None,
+        }));
+    }
 
     // Fire immediately after recording has stopped and we have audio to transcribe.
     emit_transcription_started(app);
 
-    let audio_for_save = if settings.storage.keep_audio {
-        Some(audio.clone())
-    } else {
-        None
-    };
-    let text = match transcription::transcribe(&settings, audio) {
-        Ok(text) => text,
-        Err(err) => {
-            let _ = tray::write_error(&settings, &transcripts_snapshot, &err);
-            return Err(err);
-        }
-    };
-    let created_at = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_millis() as i64)
-        .unwrap_or(0);
-
-    let title = summary::generate_title(&text);
-    let summary = summary::generate_summary(&text);
-    let embedding = embedding::embed_text(&text);
-    let id = Uuid::new_v4().to_string();
-    let audio_path = if let Some(audio) = audio_for_save {
-        storage::save_audio_recording(&settings, &id, &audio)
-            .ok()
-            .map(|path| path.to_string_lossy().to_string())
-    } else {
-        None
-    };
-    let transcript = Transcript {
-        id,
-        created_at,
+    // Transcription runs on the job worker so a new recording can start right away.
+    let recording = jobs::StoppedRecording {
+        audio,
+        started_at_ms,
         duration_ms,
-        text,
-        title,
-        summary,
-        tags: Vec::new(),
-        audio_path,
-        embedding: Some(embedding),
+        focus_window,
+        prefix: preview_prefix,
     };
+    let job = jobs::enqueue(app, state, settings, recording)?;
 
-    let mut guard = state
-        .lock()
-        .map_err(|_| "state lock poisoned".to_string())?;
-    guard.transcripts.insert(0, transcript.clone());
-    if let Err(err) = storage::upsert_transcript(&guard.settings, &transcript) {
-        guard.transcripts.retain(|item| item.id != transcript.id);
-        return Err(err);
-    }
-    let _ = tray::write_recents(&guard.settings, &guard.transcripts, Some(created_at));
-
-    let automation_settings = guard.settings.automation.clone();
-    drop(guard);
-
-    Ok(ToggleOutcome {
+    Ok(Some(ToggleOutcome {
         result: ToggleResult {
             recording: false,
-            transcript: Some(transcript),
+            job: Some(job),
         },
         started_at_ms: None,
-        focus_window_id: focus_window,
-        automation_settings: Some(automation_settings),
-    })
+    }))
 }
 
 pub fn toggle_recording_with_state_and_emit(
     app: &AppHandle,
     state: &Mutex<AppState>,
+    translate: Option<bool>,
+) -> Result<ToggleResult, String> {
+    change_recording_with_state_and_emit(app, state, None, translate)
+}
+
+/// Start (`recording`) or stop the recording unless it is already in that state, for callers
+/// that track key presses and releases rather than toggling.
+pub fn set_recording_with_state_and_emit(
+    app: &AppHandle,
+    state: &Mutex<AppState>,
+    recording: bool,
+    translate: Option<bool>,
+) -> Result<ToggleResult, String> {
+    change_recording_with_state_and_emit(app, state, Some(recording), translate)
+}
+
+fn change_recording_with_state_and_emit(
+    app: &AppHandle,
+    state: &Mutex<AppState>,
+    target: Option<bool>,
+    translate: Option<bool>,
 ) -> Result<ToggleResult, String> {
-    let outcome = toggle_recording_with_state(app, state)?;
+    let Some(outcome) = toggle_recording_with_state(app, state, target, translate)? else {
+        return Ok(ToggleResult {
+            recording: target.unwrap_or_default(),
+            job: None,
+        });
+    };
     if outcome.result.recording {
         let preview_enabled = state
             .lock()
@@ -1145,9 +1839,11 @@ pub fn toggle_recording_with_state_and_emit(
         if preview_enabled {
             start_preview_thread(app.clone(), state);
         }
+        start_limit_watch(app.clone(), state);
+        start_auto_stop_watch(app.clone(), state);
     } else {
         stop_preview_thread(state);
-        emit_preview_event(app, String::new());
+        emit_preview_event(app, String::new(), String::new());
     }
 
     // Ensure the HUD window becomes visible as soon as recording starts (if enabled).
@@ -1171,60 +1867,152 @@ pub fn toggle_recording_with_state_and_emit(
         }
     }
     emit_recording_event(app, &outcome);
-    emit_transcript_event(app, &outcome.result.transcript);
     app_tray::refresh_tray(app, state);
 
-    // Run automation (paste/copy) after state + UI have been updated.
-    if let (Some(transcript), Some(automation_settings)) = (
-        outcome.result.transcript.clone(),
-        outcome.automation_settings.clone(),
-    ) {
-        if automation_settings.auto_paste_enabled || automation_settings.copy_to_clipboard {
-            let app = app.clone();
-            let focus_window_id = outcome.focus_window_id.clone();
-            std::thread::spawn(move || {
-                let preserve = automation_settings.preserve_clipboard
-                    && !automation_settings.copy_to_clipboard;
-                let use_clipboard = automation_settings.copy_to_clipboard || preserve;
-
-                let (delay_ms, paste_method, focus) = if automation_settings.auto_paste_enabled {
-                    (
-                        automation_settings.paste_delay_ms,
-                        automation_settings.paste_method.clone(),
-                        focus_window_id.as_deref(),
-                    )
-                } else {
-                    (0, "clipboard_only".to_string(), None)
-                };
-
-                if let Err(err) = automation::paste_text(
-                    &transcript.text,
-                    delay_ms,
-                    automation_settings.clipboard_restore_delay_ms,
-                    use_clipboard,
-                    preserve,
-                    &paste_method,
-                    focus,
-                ) {
-                    // Surface error to tray (GNOME extension) and the UI.
-                    if let Ok(guard) = app.state::<Mutex<AppState>>().lock() {
-                        let _ = tray::write_error(&guard.settings, &guard.transcripts, &err);
-                    }
-                    let _ = app.emit("automation-error", AutomationErrorEvent { message: err });
-                }
-            });
+    Ok(outcome.result)
+}
+
+/// Pause or resume the current recording; `None` flips it. Returns whether it is now paused.
+pub fn set_recording_paused_with_state_and_emit(
+    app: &AppHandle,
+    state: &Mutex<AppState>,
+    paused: Option<bool>,
+) -> Result<bool, String> {
+    let (audio_tx, started_at_ms, was_paused) = {
+        let guard = state
+            .lock()
+            .map_err(|_| "state lock poisoned".to_string())?;
+        if !guard.recording {
+            return Err("Not recording".to_string());
         }
+        (
+            guard.audio_tx.clone(),
+            guard.recording_started_at_ms,
+            guard.recording_paused,
+        )
+    };
+    let paused = paused.unwrap_or(!was_paused);
+    if paused == was_paused {
+        return Ok(paused);
     }
 
-    Ok(outcome.result)
+    if paused {
+        audio::pause_recording(&audio_tx)?;
+    } else {
+        audio::resume_recording(&audio_tx)?;
+    }
+    let paused_ms = audio::stats(&audio_tx)?.paused_ms;
+    {
+        let mut guard = state
+            .lock()
+            .map_err(|_| "state lock poisoned".to_string())?;
+        // The recording may have stopped while the audio worker was busy.
+        if !guard.recording || guard.recording_started_at_ms != started_at_ms {
+            return Err("Not recording".to_string());
+        }
+        guard.recording_paused = paused;
+    }
+
+    let payload = RecordingEvent {
+        recording: true,
+        paused,
+        started_at_ms: started_at_ms.map(|started| started + i64::from(paused_ms)),
+    };
+    let _ = app.emit("recording-state", payload);
+    app_tray::refresh_tray(app, state);
+    Ok(paused)
+}
+
+/// Paste and/or copy a finished transcript according to the automation settings.
+pub fn run_automation(
+    app: &AppHandle,
+    transcript: &Transcript,
+    automation_settings: &AutomationSettings,
+    focus_window_id: Option<&str>,
+) {
+    if !automation_settings.auto_paste_enabled && !automation_settings.copy_to_clipboard {
+        return;
+    }
+    let preserve = automation_settings.preserve_clipboard && !automation_settings.copy_to_clipboard;
+    let use_clipboard = automation_settings.copy_to_clipboard || preserve;
+
+    let (delay_ms, paste_method, focus) = if automation_settings.auto_paste_enabled {
+        (
+            automation_settings.paste_delay_ms,
+            automation_settings.paste_method.clone(),
+            focus_window_id,
+        )
+    } else {
+        (0, "clipboard_only".to_string(), None)
+    };
+
+    if let Err(err) = automation::paste_text(
+        &transcript.text,
+        delay_ms,
+        automation_settings.clipboard_restore_delay_ms,
+        use_clipboard,
+        preserve,
+        &paste_method,
+        focus,
+    ) {
+        // Surface error to tray (GNOME extension) and the UI.
+        if let Ok(guard) = app.state::<Mutex<AppState>>().lock() {
+            let _ = tray::write_error(&guard.settings, &guard.transcripts, &err);
+        }
+        let _ = app.emit("automation-error", AutomationErrorEvent { message: err });
+    }
 }
 
 #[tauri::command]
 pub fn toggle_recording(
     app: AppHandle,
     state: State<'_, Mutex<AppState>>,
+    translate: Option<bool>,
+) -> Result<ToggleResult, String> {
+    toggle_recording_with_state_and_emit(&app, state.inner(), translate)
+}
+
+#[tauri::command]
+pub fn start_recording(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    translate: Option<bool>,
+) -> Result<ToggleResult, String> {
+    set_recording_with_state_and_emit(&app, state.inner(), true, translate)
+}
+
+#[tauri::command]
+pub fn stop_recording(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    translate: Option<bool>,
 ) -> Result<ToggleResult, String> {
-    toggle_recording_with_state_and_emit(&app, state.inner())
+    set_recording_with_state_and_emit(&app, state.inner(), false, translate)
+}
+
+#[tauri::command]
+pub fn pause_recording(app: AppHandle, state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    set_recording_paused_with_state_and_emit(&app, state.inner(), Some(true))
+}
+
+#[tauri::command]
+pub fn resume_recording(app: AppHandle, state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    set_recording_paused_with_state_and_emit(&app, state.inner(), Some(false))
+}
+
+#[tauri::command]
+pub fn toggle_pause(app: AppHandle, state: State<'_, Mutex<AppState>>) -> Result<bool, String> {
+    set_recording_paused_with_state_and_emit(&app, state.inner(), None)
+}
+
+#[tauri::command]
+pub fn list_transcription_jobs(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<TranscriptionJob>, String> {
+    state
+        .lock()
+        .map(|guard| guard.jobs.clone())
+        .map_err(|_| "state lock poisoned".to_string())
 }
 
 pub fn paste_last_transcript_with_state(state: &Mutex<AppState>) -> Result<bool, String> {
@@ -1274,18 +2062,36 @@ pub fn get_recording_level(state: State<'_, Mutex<AppState>>) -> Result<Option<f
 #[derive(Clone, Serialize)]
 pub struct RecordingState {
     pub recording: bool,
+    pub paused: bool,
+    /// Moved forward by the time spent paused, as in `recording-state` events.
     pub started_at_ms: Option<i64>,
     pub hud_enabled: bool,
 }
 
 #[tauri::command]
 pub fn get_recording_state(state: State<'_, Mutex<AppState>>) -> Result<RecordingState, String> {
+    let (recording, audio_tx) = {
+        let guard = state
+            .lock()
+            .map_err(|_| "state lock poisoned".to_string())?;
+        (guard.recording, guard.audio_tx.clone())
+    };
+    let paused_ms = if recording {
+        audio::stats(&audio_tx)
+            .map(|stats| stats.paused_ms)
+            .unwrap_or(0)
+    } else {
+        0
+    };
     let guard = state
         .lock()
         .map_err(|_| "state lock poisoned".to_string())?;
     Ok(RecordingState {
         recording: guard.recording,
-        started_at_ms: guard.recording_started_at_ms,
+        paused: guard.recording_paused,
+        started_at_ms: guard
+            .recording_started_at_ms
+            .map(|started| started + i64::from(paused_ms)),
         hud_enabled: guard.settings.ui.recording_hud_enabled,
     })
 }
@@ -1343,9 +2149,10 @@ pub fn delete_model(
         .map_err(|_| "state lock poisoned".to_string())?;
     models::delete_model(&guard.settings, &model_id)?;
 
+    let translate = guard.settings.transcription.translate;
     let installed = models::list_models(&guard.settings)
         .into_iter()
-        .filter(|model| model.installed)
+        .filter(|model| model.installed && (model.multilingual || !translate))
         .collect::<Vec<_>>();
 
     if installed.is_empty() {
@@ -1416,7 +2223,6 @@ pub fn activate_model(
         .map_err(|_| "state lock poisoned".to_string())?;
     models::activate_model(&mut guard.settings, &model_id)?;
     storage::save_settings(&guard.settings)?;
-    transcription::invalidate_context_cache();
     Ok(models::list_models(&guard.settings))
 }
 
@@ -1427,6 +2233,5 @@ pub fn cycle_model(state: State<'_, Mutex<AppState>>) -> Result<Vec<ModelInfo>,
         .map_err(|_| "state lock poisoned".to_string())?;
     let _ = models::cycle_model(&mut guard.settings)?;
     storage::save_settings(&guard.settings)?;
-    transcription::invalidate_context_cache();
     Ok(models::list_models(&guard.settings))
 }
@@ -1,22 +1,34 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 #[cfg(target_os = "linux")]
 use std::ffi::CString;
+use std::fs::{self, File};
+use std::io::BufWriter;
 #[cfg(target_os = "linux")]
 use std::os::raw::{c_char, c_int};
-use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicUsize, Ordering};
-use std::sync::{mpsc, Arc};
+use std::path::PathBuf;
+use std::sync::atomic::{
+    AtomicBool, AtomicI64, AtomicU16, AtomicU32, AtomicU8, AtomicUsize, Ordering,
+};
+use std::sync::{mpsc, Arc, OnceLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[cfg(target_os = "linux")]
 use alsa::card::Card;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{BufferSize, FromSample, Sample, SampleFormat, SizedSample, Stream, StreamConfig};
+use hound::{WavSpec, WavWriter};
 
 use crate::overlay;
 use crate::settings::AudioSettings;
 
-const MAX_RECORDING_SECONDS: u32 = 600;
+/// Audio kept in memory per recording. Longer recordings keep only the most recent audio unless
+/// they are spooled to disk.
+pub const MAX_RECORDING_SECONDS: u32 = 600;
+/// How often the spool writer moves captured audio from the ring buffer to disk.
+const SPOOL_INTERVAL_MS: u64 = 250;
+/// Longest pre-roll a warm input holds, whatever the settings say.
+const MAX_PRE_ROLL_MS: u32 = 2_000;
 
 #[derive(Clone, serde::Serialize)]
 pub struct AudioDevice {
@@ -26,31 +38,59 @@ pub struct AudioDevice {
 }
 
 pub enum AudioCommand {
-    Start(AudioSettings, i64, mpsc::Sender<Result<(), String>>),
+    Start(
+        AudioSettings,
+        i64,
+        Option<PathBuf>,
+        mpsc::Sender<Result<(), String>>,
+    ),
     Snapshot(usize, mpsc::Sender<Result<AudioSnapshot, String>>),
     Stats(mpsc::Sender<Result<AudioStats, String>>),
     Level(mpsc::Sender<Result<f32, String>>),
-    Stop(mpsc::Sender<Result<RecordedAudio, String>>),
+    Pause(mpsc::Sender<Result<(), String>>),
+    Resume(mpsc::Sender<Result<(), String>>),
+    Stop(mpsc::Sender<Result<CapturedAudio, String>>),
+    /// Keep the input open with a pre-roll between recordings (`Some`), or close it (`None`).
+    Warm(Option<AudioSettings>, mpsc::Sender<Result<(), String>>),
 }
 
 pub fn start_worker() -> mpsc::Sender<AudioCommand> {
     let (tx, rx) = mpsc::channel();
     thread::spawn(move || {
         let mut recorder: Option<Recorder> = None;
+        // Settings for the warm input kept between recordings, and the input itself.
+        let mut warm_settings: Option<AudioSettings> = None;
+        let mut warm: Option<Recorder> = None;
         for command in rx {
             match command {
-                AudioCommand::Start(settings, started_at_ms, reply) => {
+                AudioCommand::Start(settings, started_at_ms, spool_path, reply) => {
                     if recorder.is_some() {
                         let _ = reply.send(Err("Recorder already running".to_string()));
                         continue;
                     }
-                    match Recorder::start(&settings, started_at_ms) {
+                    // The warm input can only be used if it was opened with these settings and
+                    // still captures from the device they select.
+                    let warm_fits = warm_settings.as_ref() == Some(&settings);
+                    let opened = match warm.take() {
+                        Some(input) if warm_fits && input.still_current(&settings) => Ok(input),
+                        stale => {
+                            drop(stale);
+                            Recorder::open(&settings, false)
+                        }
+                    };
+                    let _ = overlay::set_mic_warm(false);
+                    let started = opened.and_then(|mut input| {
+                        input.begin(started_at_ms, spool_path)?;
+                        Ok(input)
+                    });
+                    match started {
                         Ok(active) => {
                             recorder = Some(active);
                             let _ = reply.send(Ok(()));
                         }
                         Err(err) => {
                             let _ = reply.send(Err(err));
+                            warm = rewarm(warm_settings.as_ref());
                         }
                     }
                 }
@@ -58,6 +98,7 @@ pub fn start_worker() -> mpsc::Sender<AudioCommand> {
                     Some(active) => {
                         let result = active.stop();
                         let _ = reply.send(result);
+                        warm = rewarm(warm_settings.as_ref());
                     }
                     None => {
                         let _ = reply.send(Err("No active recorder found".to_string()));
@@ -88,26 +129,104 @@ pub fn start_worker() -> mpsc::Sender<AudioCommand> {
                         let _ = reply.send(Err("No active recorder found".to_string()));
                     }
                 },
+                AudioCommand::Pause(reply) => match recorder.as_ref() {
+                    Some(active) => {
+                        active.pause();
+                        let _ = reply.send(Ok(()));
+                    }
+                    None => {
+                        let _ = reply.send(Err("No active recorder found".to_string()));
+                    }
+                },
+                AudioCommand::Resume(reply) => match recorder.as_ref() {
+                    Some(active) => {
+                        active.resume();
+                        let _ = reply.send(Ok(()));
+                    }
+                    None => {
+                        let _ = reply.send(Err("No active recorder found".to_string()));
+                    }
+                },
+                AudioCommand::Warm(settings, reply) => {
+                    warm_settings = settings;
+                    // A running recording picks the new settings up when it stops.
+                    if recorder.is_some() {
+                        let _ = reply.send(Ok(()));
+                        continue;
+                    }
+                    warm = None;
+                    let result = open_warm(warm_settings.as_ref()).map(|input| warm = input);
+                    let _ = reply.send(result);
+                }
             }
         }
     });
     tx
 }
 
+/// The warm input for `settings`, if pre-roll is on. The overlay shows whether the mic is open.
+fn open_warm(settings: Option<&AudioSettings>) -> Result<Option<Recorder>, String> {
+    let input = settings
+        .map(|settings| Recorder::open(settings, true))
+        .transpose();
+    let warm = matches!(input, Ok(Some(_)));
+    let _ = overlay::set_mic_warm(warm);
+    input
+}
+
+/// `open_warm` after a recording, when there is no caller to report a failure to.
+fn rewarm(settings: Option<&AudioSettings>) -> Option<Recorder> {
+    open_warm(settings).unwrap_or_else(|err| {
+        log_warm_failure(&err);
+        None
+    })
+}
+
+/// A warm input is a convenience, so failing to open one is only logged; recording still works.
+fn log_warm_failure(err: &str) {
+    eprintln!("Failed to keep the microphone warm: {err}");
+}
+
+/// Keep the microphone open between recordings while pre-roll is on, so a recording starts with
+/// the moment before it was triggered; close it when pre-roll is off.
+pub fn update_warm_capture(tx: &mpsc::Sender<AudioCommand>, settings: &AudioSettings) {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    let warm = settings.pre_roll.then(|| settings.clone());
+    let result = tx
+        .send(AudioCommand::Warm(warm, reply_tx))
+        .map_err(|_| "Audio worker unavailable".to_string())
+        .and_then(|_| {
+            reply_rx
+                .recv()
+                .map_err(|_| "Audio worker unavailable".to_string())?
+        });
+    if let Err(err) = result {
+        log_warm_failure(&err);
+    }
+}
+
+/// Start capturing. With `spool_path`, everything captured is also written to that WAV file so
+/// the recording isn't limited to `MAX_RECORDING_SECONDS`.
 pub fn start_recording(
     tx: &mpsc::Sender<AudioCommand>,
     settings: AudioSettings,
     started_at_ms: i64,
+    spool_path: Option<PathBuf>,
 ) -> Result<(), String> {
     let (reply_tx, reply_rx) = mpsc::channel();
-    tx.send(AudioCommand::Start(settings, started_at_ms, reply_tx))
-        .map_err(|_| "Audio worker unavailable".to_string())?;
+    tx.send(AudioCommand::Start(
+        settings,
+        started_at_ms,
+        spool_path,
+        reply_tx,
+    ))
+    .map_err(|_| "Audio worker unavailable".to_string())?;
     reply_rx
         .recv()
         .map_err(|_| "Audio worker unavailable".to_string())?
 }
 
-pub fn stop_recording(tx: &mpsc::Sender<AudioCommand>) -> Result<RecordedAudio, String> {
+pub fn stop_recording(tx: &mpsc::Sender<AudioCommand>) -> Result<CapturedAudio, String> {
     let (reply_tx, reply_rx) = mpsc::channel();
     tx.send(AudioCommand::Stop(reply_tx))
         .map_err(|_| "Audio worker unavailable".to_string())?;
@@ -146,6 +265,25 @@ pub fn recording_level(tx: &mpsc::Sender<AudioCommand>) -> Result<f32, String> {
         .map_err(|_| "Audio worker unavailable".to_string())?
 }
 
+/// Stop keeping audio while leaving the input stream open, so resuming is instant.
+pub fn pause_recording(tx: &mpsc::Sender<AudioCommand>) -> Result<(), String> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    tx.send(AudioCommand::Pause(reply_tx))
+        .map_err(|_| "Audio worker unavailable".to_string())?;
+    reply_rx
+        .recv()
+        .map_err(|_| "Audio worker unavailable".to_string())?
+}
+
+pub fn resume_recording(tx: &mpsc::Sender<AudioCommand>) -> Result<(), String> {
+    let (reply_tx, reply_rx) = mpsc::channel();
+    tx.send(AudioCommand::Resume(reply_tx))
+        .map_err(|_| "Audio worker unavailable".to_string())?;
+    reply_rx
+        .recv()
+        .map_err(|_| "Audio worker unavailable".to_string())?
+}
+
 pub fn list_input_devices() -> Vec<AudioDevice> {
     silence_alsa_errors();
     let host = cpal::default_host();
@@ -334,8 +472,94 @@ pub struct Recorder {
     channels: u16,
     meter_stop: Arc<AtomicBool>,
     meter_thread: Option<thread::JoinHandle<()>>,
-    active: Arc<AtomicBool>,
+    flags: StreamFlags,
     level: Arc<AtomicU16>,
+    pauses: Arc<PauseClock>,
+    spool: Option<Spool>,
+    /// Name of the device the stream was opened on.
+    device_name: Option<String>,
+}
+
+/// Switches shared between a recorder and its input callback.
+#[derive(Clone, Default)]
+struct StreamFlags {
+    /// Keep what is captured: recording and not paused.
+    active: Arc<AtomicBool>,
+    /// While not active, hold the last moments of input for the recording to start with.
+    warm: Arc<AtomicBool>,
+    /// Set by the callback once auto-stop decides the speaker is done (`AutoStopReason::to_raw`).
+    auto_stop: Arc<AtomicU8>,
+    /// Set when the stream reports an error, e.g. after the device was unplugged.
+    failed: Arc<AtomicBool>,
+}
+
+/// Time a recording has spent paused, shared with the meter thread so the overlay clock can
+/// leave it out.
+#[derive(Default)]
+struct PauseClock {
+    /// When the current pause began; 0 while capturing.
+    since_ms: AtomicI64,
+    /// Length of the pauses that have ended.
+    total_ms: AtomicI64,
+}
+
+impl PauseClock {
+    /// Returns false if already paused.
+    fn pause(&self, now_ms: i64) -> bool {
+        self.since_ms
+            .compare_exchange(0, now_ms.max(1), Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Returns false if not paused.
+    fn resume(&self, now_ms: i64) -> bool {
+        let since = self.since_ms.swap(0, Ordering::AcqRel);
+        if since == 0 {
+            return false;
+        }
+        self.total_ms
+            .fetch_add((now_ms - since).max(0), Ordering::AcqRel);
+        true
+    }
+
+    fn paused(&self) -> bool {
+        self.since_ms.load(Ordering::Acquire) != 0
+    }
+
+    fn finished_ms(&self) -> i64 {
+        self.total_ms.load(Ordering::Acquire)
+    }
+
+    /// Paused time up to `now_ms`, counting a pause that is still going on.
+    fn paused_ms(&self, now_ms: i64) -> i64 {
+        let since = self.since_ms.load(Ordering::Acquire);
+        let current = if since == 0 {
+            0
+        } else {
+            (now_ms - since).max(0)
+        };
+        self.total_ms.load(Ordering::Acquire) + current
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Background writer copying the ring buffer to a spool file as audio arrives.
+struct Spool {
+    stop: Arc<AtomicBool>,
+    thread: thread::JoinHandle<Result<PathBuf, String>>,
+}
+
+/// What a stopped recording hands over for transcription.
+pub enum CapturedAudio {
+    Memory(RecordedAudio),
+    /// Mono WAV file holding the whole recording, however long.
+    Spooled(PathBuf),
 }
 
 #[derive(Clone)]
@@ -357,30 +581,74 @@ pub struct AudioStats {
     pub total_samples: usize,
     pub sample_rate: u32,
     pub channels: u16,
+    /// Time spent paused so far, which the recording's duration leaves out.
+    pub paused_ms: u32,
+    /// Auto-stop is on and has decided the recording is over.
+    pub auto_stop: Option<AutoStopReason>,
+}
+
+/// Why auto-stop ended a recording.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoStopReason {
+    /// Speech was followed by the configured stretch of silence.
+    Silence,
+    /// Nobody spoke before the no-speech timeout.
+    NoSpeech,
+}
+
+impl AutoStopReason {
+    fn to_raw(self) -> u8 {
+        match self {
+            Self::Silence => 1,
+            Self::NoSpeech => 2,
+        }
+    }
+
+    fn from_raw(raw: u8) -> Option<Self> {
+        match raw {
+            1 => Some(Self::Silence),
+            2 => Some(Self::NoSpeech),
+            _ => None,
+        }
+    }
+}
+
+impl AudioStats {
+    pub fn recorded_seconds(&self) -> u32 {
+        let frames = self.total_samples / self.channels.max(1) as usize;
+        (frames / self.sample_rate.max(1) as usize) as u32
+    }
 }
 
 // Single-producer (CPAL callback) and single-reader (audio worker thread).
 // Uses atomic slots so the callback never takes a mutex.
+// The slots are allocated on first use, so a warm input that isn't recording holds no ring.
 struct AudioRingBuffer {
     cap: usize,
-    data: Vec<AtomicU32>,
+    data: OnceLock<Box<[AtomicU32]>>,
     head: AtomicUsize, // total written samples (monotonic)
     tail: AtomicUsize, // oldest retained sample index (monotonic)
 }
 
 impl AudioRingBuffer {
     fn new(cap: usize) -> Self {
-        let cap = cap.max(1);
-        let data = (0..cap).map(|_| AtomicU32::new(0)).collect();
         Self {
-            cap,
-            data,
+            cap: cap.max(1),
+            data: OnceLock::new(),
             head: AtomicUsize::new(0),
             tail: AtomicUsize::new(0),
         }
     }
 
+    fn allocate(&self) {
+        self.data
+            .get_or_init(|| (0..self.cap).map(|_| AtomicU32::new(0)).collect());
+    }
+
     fn push_slice(&self, input: &[f32]) {
+        let Some(data) = self.data.get() else {
+            return;
+        };
         if input.is_empty() {
             return;
         }
@@ -389,7 +657,7 @@ impl AudioRingBuffer {
         let mut head = self.head.load(Ordering::Relaxed);
         let mut idx = head % cap;
         for &sample in input {
-            self.data[idx].store(sample.to_bits(), Ordering::Relaxed);
+            data[idx].store(sample.to_bits(), Ordering::Relaxed);
             head = head.wrapping_add(1);
             idx += 1;
             if idx == cap {
@@ -415,7 +683,9 @@ impl AudioRingBuffer {
         // needed) and retry a bounded number of times when the base becomes stale.
         const SNAPSHOT_RETRIES: usize = 3;
         let cap = self.cap;
-        let data = &self.data;
+        let Some(data) = self.data.get() else {
+            return (Vec::new(), self.head.load(Ordering::Acquire));
+        };
 
         for _ in 0..SNAPSHOT_RETRIES {
             let mut head = self.head.load(Ordering::Acquire);
@@ -477,11 +747,22 @@ impl AudioRingBuffer {
 
 #[cfg(test)]
 mod ring_tests {
-    use super::AudioRingBuffer;
+    use super::*;
+
+    #[test]
+    fn ring_keeps_nothing_until_allocated() {
+        let ring = AudioRingBuffer::new(8);
+        ring.push_slice(&[1.0, 2.0]);
+        assert_eq!(ring.snapshot_from(0), (Vec::new(), 0));
+        ring.allocate();
+        ring.push_slice(&[3.0]);
+        assert_eq!(ring.snapshot_from(0), (vec![3.0], 1));
+    }
 
     #[test]
     fn ring_snapshot_returns_all_when_from_is_zero() {
         let ring = AudioRingBuffer::new(8);
+        ring.allocate();
         ring.push_slice(&[1.0, 2.0, 3.0]);
         let (samples, total) = ring.snapshot_from(0);
         assert_eq!(total, 3);
@@ -491,6 +772,7 @@ mod ring_tests {
     #[test]
     fn ring_snapshot_from_cursor_returns_incremental() {
         let ring = AudioRingBuffer::new(8);
+        ring.allocate();
         ring.push_slice(&[1.0, 2.0, 3.0, 4.0]);
         let (_samples, cursor) = ring.snapshot_from(0);
         ring.push_slice(&[5.0, 6.0]);
@@ -499,9 +781,98 @@ mod ring_tests {
         assert_eq!(samples, vec![5.0, 6.0]);
     }
 
+    #[test]
+    fn spool_keeps_more_than_the_ring_holds() {
+        let path = std::env::temp_dir().join(format!("whispr-spool-{}.wav", uuid::Uuid::new_v4()));
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 16_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).expect("writer");
+        let ring = AudioRingBuffer::new(4);
+        ring.allocate();
+        ring.push_slice(&[0.5, 0.5, 0.25, 0.25]);
+        let cursor = spool_from(&ring, 0, 2, &mut writer).expect("spool");
+        ring.push_slice(&[-0.5, -0.5, 1.0, 0.0]);
+        let cursor = spool_from(&ring, cursor, 2, &mut writer).expect("spool");
+        assert_eq!(spool_from(&ring, cursor, 2, &mut writer).expect("spool"), 8);
+        writer.finalize().expect("finalize");
+
+        let samples: Vec<i16> = hound::WavReader::open(&path)
+            .expect("reader")
+            .samples::<i16>()
+            .map(|sample| sample.expect("sample"))
+            .collect();
+        let full = i16::MAX as f32;
+        let expected = [0.5, 0.25, -0.5, 0.5].map(|value: f32| (value * full) as i16);
+        assert_eq!(samples, expected);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn pause_clock_counts_finished_and_ongoing_pauses() {
+        let clock = PauseClock::default();
+        assert!(!clock.resume(1_000));
+        assert!(clock.pause(1_000));
+        assert!(!clock.pause(1_500));
+        assert_eq!(clock.paused_ms(1_800), 800);
+        assert!(clock.resume(2_000));
+        assert!(!clock.paused());
+        assert_eq!(clock.finished_ms(), 1_000);
+        assert!(clock.pause(5_000));
+        assert_eq!(clock.paused_ms(5_250), 1_250);
+        assert!(clock.resume(5_500));
+        assert_eq!(clock.paused_ms(9_000), 1_500);
+    }
+
+    #[test]
+    fn auto_stop_waits_for_silence_after_speech() {
+        let mut auto_stop = AutoStop {
+            silence_ms: 1_000,
+            no_speech_ms: 5_000,
+            onset_ms: 200,
+            ..AutoStop::default()
+        };
+        // A short click is not speech, so the quiet before and after doesn't end anything.
+        assert_eq!(auto_stop.update(true, 100), None);
+        for _ in 0..20 {
+            assert_eq!(auto_stop.update(false, 100), None);
+        }
+        for _ in 0..3 {
+            assert_eq!(auto_stop.update(true, 100), None);
+        }
+        for _ in 0..9 {
+            assert_eq!(auto_stop.update(false, 100), None);
+        }
+        // Once speech has been heard, any speech restarts the silence.
+        assert_eq!(auto_stop.update(true, 100), None);
+        for _ in 0..9 {
+            assert_eq!(auto_stop.update(false, 100), None);
+        }
+        assert_eq!(auto_stop.update(false, 100), Some(AutoStopReason::Silence));
+    }
+
+    #[test]
+    fn auto_stop_times_out_without_speech() {
+        let mut auto_stop = AutoStop {
+            silence_ms: 1_000,
+            no_speech_ms: 3_000,
+            onset_ms: 200,
+            ..AutoStop::default()
+        };
+        for _ in 0..29 {
+            assert_eq!(auto_stop.update(false, 100), None);
+        }
+        assert_eq!(auto_stop.update(false, 100), Some(AutoStopReason::NoSpeech));
+    }
+
     #[test]
     fn ring_overwrite_keeps_latest_cap_samples() {
         let ring = AudioRingBuffer::new(4);
+        ring.allocate();
         ring.push_slice(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
         let (samples, total) = ring.snapshot_from(0);
         assert_eq!(total, 6);
@@ -510,15 +881,25 @@ mod ring_tests {
 }
 
 impl Recorder {
-    pub fn start(settings: &AudioSettings, started_at_ms: i64) -> Result<Self, String> {
+    /// Open the input without keeping anything yet. A `warm` recorder holds the last
+    /// `pre_roll_ms` of input in memory, for `begin` to start the recording with.
+    pub fn open(settings: &AudioSettings, warm: bool) -> Result<Self, String> {
         silence_alsa_errors();
         let host = cpal::default_host();
         let device = select_device(&host, &settings.input_device_id)?;
         let (config, sample_format) = select_config(&device, settings)?;
+        let device_name = device.name().ok();
 
-        let active = Arc::new(AtomicBool::new(true));
+        let flags = StreamFlags::default();
+        flags.warm.store(warm, Ordering::Relaxed);
         let level = Arc::new(AtomicU16::new(0));
         let meter_stop = Arc::new(AtomicBool::new(false));
+        let pre_roll_ms = if warm {
+            settings.pre_roll_ms.min(MAX_PRE_ROLL_MS)
+        } else {
+            0
+        };
+        let pre_roll_frames = config.sample_rate.0 as usize * pre_roll_ms as usize / 1000;
 
         let processing = AudioProcessingConfig {
             gain: db_to_gain(settings.input_gain_db),
@@ -528,6 +909,13 @@ impl Recorder {
             vad_threshold: settings.vad_threshold.clamp(0.0, 1.0),
             vad_silence_ms: settings.vad_silence_ms,
             vad_resume_ms: settings.vad_resume_ms,
+            auto_stop: settings.auto_stop.then(|| AutoStop {
+                silence_ms: settings.auto_stop_silence_ms,
+                no_speech_ms: settings.auto_stop_no_speech_ms,
+                onset_ms: settings.vad_resume_ms,
+                ..AutoStop::default()
+            }),
+            pre_roll_samples: pre_roll_frames * config.channels.max(1) as usize,
         };
 
         let sample_rate = config.sample_rate.0.max(1);
@@ -542,7 +930,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -550,7 +938,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -558,7 +946,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -566,7 +954,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -574,7 +962,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -582,7 +970,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -590,7 +978,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -598,7 +986,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -606,7 +994,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -614,7 +1002,7 @@ impl Recorder {
                 &device,
                 &config,
                 samples.clone(),
-                active.clone(),
+                flags.clone(),
                 level.clone(),
                 processing,
             )?,
@@ -623,44 +1011,103 @@ impl Recorder {
 
         stream.play().map_err(|err| err.to_string())?;
 
-        let meter_level = level.clone();
-        let meter_stop_flag = meter_stop.clone();
-        let meter_thread = thread::spawn(move || {
-            while !meter_stop_flag.load(Ordering::Relaxed) {
-                let raw = meter_level.load(Ordering::Relaxed) as f32;
-                let normalized = (raw / 1000.0).clamp(0.0, 1.0);
-                let _ = overlay::write_state(true, Some(started_at_ms), Some(normalized));
-                thread::sleep(Duration::from_millis(120));
-            }
-        });
-
         Ok(Self {
             stream,
             samples,
             sample_rate: config.sample_rate.0,
             channels: config.channels,
             meter_stop,
-            meter_thread: Some(meter_thread),
-            active,
+            meter_thread: None,
+            flags,
             level,
+            pauses: Arc::new(PauseClock::default()),
+            spool: None,
+            device_name,
         })
     }
 
-    pub fn stop(mut self) -> Result<RecordedAudio, String> {
+    /// Whether the open stream is still healthy and on the device `settings` would open now.
+    /// The system default can change while a warm stream stays on the old one.
+    pub fn still_current(&self, settings: &AudioSettings) -> bool {
+        if self.flags.failed.load(Ordering::Relaxed) {
+            return false;
+        }
+        if settings.input_device_id != "default" {
+            return true;
+        }
+        let default_name = cpal::default_host()
+            .default_input_device()
+            .and_then(|device| device.name().ok());
+        default_name.is_some() && default_name == self.device_name
+    }
+
+    /// Start keeping audio, beginning with the pre-roll when the recorder was warm.
+    pub fn begin(&mut self, started_at_ms: i64, spool_path: Option<PathBuf>) -> Result<(), String> {
+        self.samples.allocate();
+        if let Some(path) = spool_path {
+            self.spool = Some(Spool::start(
+                self.samples.clone(),
+                path,
+                self.sample_rate,
+                self.channels,
+            )?);
+        }
+
+        let meter_level = self.level.clone();
+        let meter_stop_flag = self.meter_stop.clone();
+        let meter_pauses = self.pauses.clone();
+        let meter_thread = thread::spawn(move || {
+            while !meter_stop_flag.load(Ordering::Relaxed) {
+                let raw = meter_level.load(Ordering::Relaxed) as f32;
+                let normalized = (raw / 1000.0).clamp(0.0, 1.0);
+                // The start moves past finished pauses; readers hold their clock while paused.
+                let started_at_ms = started_at_ms + meter_pauses.finished_ms();
+                let _ = overlay::write_state(
+                    true,
+                    meter_pauses.paused(),
+                    Some(started_at_ms),
+                    Some(normalized),
+                );
+                thread::sleep(Duration::from_millis(120));
+            }
+        });
+        self.meter_thread = Some(meter_thread);
+
+        // The callback moves the pre-roll into the ring before the first active chunk.
+        self.flags.active.store(true, Ordering::Relaxed);
+        self.flags.warm.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn stop(mut self) -> Result<CapturedAudio, String> {
         self.meter_stop.store(true, Ordering::Relaxed);
         if let Some(handle) = self.meter_thread.take() {
             let _ = handle.join();
         }
         // Stop accepting callback writes before pausing/dropping the stream.
-        self.active.store(false, Ordering::Relaxed);
+        self.flags.active.store(false, Ordering::Relaxed);
+        self.flags.warm.store(false, Ordering::Relaxed);
         let _ = self.stream.pause();
+
+        if let Some(spool) = self.spool.take() {
+            match spool.finish() {
+                // Recordings the ring still holds in full are handed over from memory like any
+                // other, so short dictations keep the fast path.
+                Ok(path) if self.samples.total_samples() <= self.samples.cap => {
+                    let _ = fs::remove_file(&path);
+                }
+                Ok(path) => return Ok(CapturedAudio::Spooled(path)),
+                // The ring buffer still holds the most recent audio.
+                Err(err) => eprintln!("Recording spool failed, keeping the last part only: {err}"),
+            }
+        }
         let (samples, _total_samples) = self.samples.snapshot_from(0);
 
-        Ok(RecordedAudio {
+        Ok(CapturedAudio::Memory(RecordedAudio {
             samples,
             sample_rate: self.sample_rate,
             channels: self.channels,
-        })
+        }))
     }
 
     pub fn snapshot(&self, from_index: usize) -> Result<AudioSnapshot, String> {
@@ -678,6 +1125,22 @@ impl Recorder {
             total_samples: self.samples.total_samples(),
             sample_rate: self.sample_rate,
             channels: self.channels,
+            paused_ms: self.pauses.paused_ms(now_ms()).clamp(0, u32::MAX as i64) as u32,
+            auto_stop: AutoStopReason::from_raw(self.flags.auto_stop.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// The callback drops audio while paused; the stream itself keeps running.
+    pub fn pause(&self) {
+        if self.pauses.pause(now_ms()) {
+            self.flags.active.store(false, Ordering::Relaxed);
+            self.level.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn resume(&self) {
+        if self.pauses.resume(now_ms()) {
+            self.flags.active.store(true, Ordering::Relaxed);
         }
     }
 
@@ -687,6 +1150,71 @@ impl Recorder {
     }
 }
 
+impl Spool {
+    fn start(
+        ring: Arc<AudioRingBuffer>,
+        path: PathBuf,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Self, String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).map_err(|err| err.to_string())?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = stop.clone();
+        let thread = thread::spawn(move || {
+            let mut cursor = 0;
+            loop {
+                // Read the flag first so the final drain sees everything captured before it.
+                let stopping = stop_flag.load(Ordering::Relaxed);
+                cursor = spool_from(&ring, cursor, channels, &mut writer)?;
+                if stopping {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(SPOOL_INTERVAL_MS));
+            }
+            writer.finalize().map_err(|err| err.to_string())?;
+            Ok(path)
+        });
+        Ok(Self { stop, thread })
+    }
+
+    fn finish(self) -> Result<PathBuf, String> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.thread
+            .join()
+            .map_err(|_| "Recording spool writer panicked".to_string())?
+    }
+}
+
+/// Append the samples captured since `cursor` to `writer`, downmixed to mono, and return the new
+/// cursor.
+fn spool_from(
+    ring: &AudioRingBuffer,
+    cursor: usize,
+    channels: u16,
+    writer: &mut WavWriter<BufWriter<File>>,
+) -> Result<usize, String> {
+    let (samples, total) = ring.snapshot_from(cursor);
+    if samples.is_empty() {
+        // Nothing new, or a read raced the producer; try again next time.
+        return Ok(cursor.min(total));
+    }
+    for frame in samples.chunks(channels.max(1) as usize) {
+        let mono = frame.iter().sum::<f32>() / frame.len() as f32;
+        let value = (mono.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        writer.write_sample(value).map_err(|err| err.to_string())?;
+    }
+    Ok(total)
+}
+
 fn select_device(host: &cpal::Host, input_device_id: &str) -> Result<cpal::Device, String> {
     if input_device_id != "default" {
         if let Ok(mut devices) = host.input_devices() {
@@ -781,13 +1309,52 @@ struct AudioProcessingConfig {
     vad_threshold: f32,
     vad_silence_ms: u32,
     vad_resume_ms: u32,
+    auto_stop: Option<AutoStop>,
+    /// Interleaved samples a warm stream holds before recording begins.
+    pre_roll_samples: usize,
+}
+
+/// Hands-free end of a recording: a stretch of silence once speech has been heard, or a timeout
+/// when it never is. Speech is judged with the VAD threshold, whether or not VAD is on.
+#[derive(Clone, Copy, Debug, Default)]
+struct AutoStop {
+    silence_ms: u32,
+    no_speech_ms: u32,
+    /// Speech has to last this long before it counts, so a click or cough doesn't.
+    onset_ms: u32,
+    heard_speech: bool,
+    speech_run_ms: u32,
+    quiet_ms: u32,
+    elapsed_ms: u32,
+}
+
+impl AutoStop {
+    /// Account for one chunk of input; the reason to end the recording once it should.
+    fn update(&mut self, speech: bool, chunk_ms: u32) -> Option<AutoStopReason> {
+        self.elapsed_ms = self.elapsed_ms.saturating_add(chunk_ms);
+        if speech {
+            self.speech_run_ms = self.speech_run_ms.saturating_add(chunk_ms);
+            if self.heard_speech || self.speech_run_ms >= self.onset_ms {
+                self.heard_speech = true;
+                self.quiet_ms = 0;
+            }
+        } else {
+            self.speech_run_ms = 0;
+            self.quiet_ms = self.quiet_ms.saturating_add(chunk_ms);
+        }
+        if self.heard_speech {
+            (self.quiet_ms >= self.silence_ms).then_some(AutoStopReason::Silence)
+        } else {
+            (self.elapsed_ms >= self.no_speech_ms).then_some(AutoStopReason::NoSpeech)
+        }
+    }
 }
 
 fn build_stream<T>(
     device: &cpal::Device,
     config: &StreamConfig,
     samples: Arc<AudioRingBuffer>,
-    active: Arc<AtomicBool>,
+    flags: StreamFlags,
     level: Arc<AtomicU16>,
     processing: AudioProcessingConfig,
 ) -> Result<Stream, String>
@@ -803,10 +1370,14 @@ where
         vad_threshold,
         vad_silence_ms,
         vad_resume_ms,
+        mut auto_stop,
+        pre_roll_samples,
     } = processing;
 
-    let err_fn = |err| {
+    let failed = flags.failed.clone();
+    let err_fn = move |err| {
         eprintln!("Audio input stream error: {err}");
+        failed.store(true, Ordering::Relaxed);
     };
 
     let mut vad_state = VadState {
@@ -817,12 +1388,15 @@ where
     let sample_rate = config.sample_rate.0.max(1);
     let channels = config.channels.max(1) as usize;
     let mut scratch: Vec<f32> = Vec::new();
+    // Only ever in memory; dropped with the stream.
+    let mut pre_roll: VecDeque<f32> = VecDeque::with_capacity(pre_roll_samples);
 
     device
         .build_input_stream(
             config,
             move |data: &[T], _| {
-                if !active.load(Ordering::Relaxed) {
+                let active = flags.active.load(Ordering::Relaxed);
+                if !active && !flags.warm.load(Ordering::Relaxed) {
                     return;
                 }
                 if data.is_empty() {
@@ -839,19 +1413,36 @@ where
                     scratch.push(value);
                 }
 
+                if !active {
+                    pre_roll.extend(scratch.iter().copied());
+                    let excess = pre_roll.len().saturating_sub(pre_roll_samples);
+                    pre_roll.drain(..excess);
+                    return;
+                }
+                if !pre_roll.is_empty() {
+                    samples.push_slice(pre_roll.make_contiguous());
+                    pre_roll.clear();
+                }
+
                 let rms = (sum / data.len() as f32).sqrt();
                 let normalized = (rms * 2.5).clamp(0.0, 1.0);
                 level.store((normalized * 1000.0) as u16, Ordering::Relaxed);
 
-                if vad_enabled {
-                    let frames = data.len() / channels;
-                    let chunk_ms = if sample_rate > 0 {
-                        ((frames as u64).saturating_mul(1000) / sample_rate as u64) as u32
-                    } else {
-                        0
-                    };
-                    let speech = rms >= vad_threshold;
+                let frames = data.len() / channels;
+                let chunk_ms = if sample_rate > 0 {
+                    ((frames as u64).saturating_mul(1000) / sample_rate as u64) as u32
+                } else {
+                    0
+                };
+                let speech = rms >= vad_threshold;
+
+                if let Some(auto_stop) = auto_stop.as_mut() {
+                    if let Some(reason) = auto_stop.update(speech, chunk_ms) {
+                        flags.auto_stop.store(reason.to_raw(), Ordering::Relaxed);
+                    }
+                }
 
+                if vad_enabled {
                     if vad_state.active {
                         if speech {
                             vad_state.silence_ms = 0;
@@ -0,0 +1,110 @@
+use crate::types::BenchmarkResult;
+
+/// Configurations this much slower than the fastest still count as fast, so the recommendation
+/// can leave cores free for everything else.
+const RECOMMENDATION_TOLERANCE: f32 = 0.05;
+
+/// Thread counts worth comparing on a machine with `available` hardware threads: powers of two
+/// and the full count.
+pub fn thread_counts(available: u32) -> Vec<u32> {
+    let available = available.max(1);
+    let mut counts: Vec<u32> = std::iter::successors(Some(1_u32), |count| count.checked_mul(2))
+        .take_while(|count| *count < available)
+        .collect();
+    counts.push(available);
+    counts
+}
+
+/// The configuration to use for `model`: the fewest threads, preferring CPU, that come within
+/// `RECOMMENDATION_TOLERANCE` of the fastest warm run.
+pub fn recommend<'a>(results: &'a [BenchmarkResult], model: &str) -> Option<&'a BenchmarkResult> {
+    let candidates: Vec<&BenchmarkResult> = results
+        .iter()
+        .filter(|result| result.model == model)
+        .collect();
+    let fastest = candidates
+        .iter()
+        .map(|result| result.realtime_factor)
+        .fold(0.0, f32::max);
+    candidates
+        .into_iter()
+        .filter(|result| result.realtime_factor >= fastest * (1.0 - RECOMMENDATION_TOLERANCE))
+        .min_by_key(|result| (result.threads, result.gpu))
+}
+
+/// Start measuring peak memory from the current usage. Returns false when the platform can't.
+#[cfg(target_os = "linux")]
+pub fn reset_peak_memory() -> bool {
+    // "5" resets the VmHWM high-water mark (Linux 4.0+).
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn reset_peak_memory() -> bool {
+    false
+}
+
+/// Highest resident memory of this process since `reset_peak_memory`.
+#[cfg(target_os = "linux")]
+pub fn peak_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn peak_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(model: &str, threads: u32, gpu: bool, realtime_factor: f32) -> BenchmarkResult {
+        BenchmarkResult {
+            id: format!("{model}-{threads}-{gpu}"),
+            run_id: "run".to_string(),
+            created_at: 0,
+            model: model.to_string(),
+            threads,
+            gpu,
+            cold_load_ms: 500,
+            audio_seconds: 10.0,
+            duration_ms: 1_000,
+            realtime_factor,
+            peak_memory_bytes: None,
+            gpu_name: None,
+            cpu_count: 8,
+        }
+    }
+
+    #[test]
+    fn thread_counts_double_up_to_the_machine() {
+        assert_eq!(thread_counts(12), vec![1, 2, 4, 8, 12]);
+        assert_eq!(thread_counts(8), vec![1, 2, 4, 8]);
+        assert_eq!(thread_counts(0), vec![1]);
+    }
+
+    #[test]
+    fn recommends_the_fewest_threads_near_the_fastest() {
+        let results = vec![
+            result("base.en", 1, false, 3.0),
+            result("base.en", 4, false, 9.7),
+            result("base.en", 8, false, 10.0),
+            result("base.en", 8, true, 9.8),
+            result("tiny.en", 8, false, 30.0),
+        ];
+        let best = recommend(&results, "base.en").expect("recommendation");
+        assert_eq!((best.threads, best.gpu), (4, false));
+
+        let results = vec![
+            result("base.en", 4, false, 5.0),
+            result("base.en", 1, true, 20.0),
+        ];
+        let best = recommend(&results, "base.en").expect("recommendation");
+        assert_eq!((best.threads, best.gpu), (1, true));
+        assert!(recommend(&results, "small.en").is_none());
+    }
+}
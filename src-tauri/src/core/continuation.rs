@@ -0,0 +1,149 @@
+/// Whisper reads at most half of its 448-token text context as the initial prompt.
+const PROMPT_TOKEN_BUDGET: usize = 224;
+/// Backends without a tokenizer (HTTP) estimate prompt length from characters. Whisper averages
+/// about four characters per token in English; three leaves room for names.
+const CHARS_PER_TOKEN: usize = 3;
+
+/// The last finished dictation, whose wording the next one can pick up from.
+#[derive(Debug, Clone)]
+pub struct PreviousDictation {
+    pub text: String,
+    pub ended_at_ms: i64,
+    /// Window the dictation was pasted into.
+    pub focus_window: Option<String>,
+}
+
+/// Whether a recording started at `started_at_ms` into `focus_window` carries on from
+/// `previous`: it began within `window_secs` of the previous one ending, or in the same window.
+pub fn continues(
+    previous: &PreviousDictation,
+    started_at_ms: i64,
+    focus_window: Option<&str>,
+    window_secs: u32,
+) -> bool {
+    let gap_ms = started_at_ms - previous.ended_at_ms;
+    let recent = (0..=i64::from(window_secs) * 1000).contains(&gap_ms);
+    let same_window = focus_window.is_some() && previous.focus_window.as_deref() == focus_window;
+    recent || same_window
+}
+
+/// Rough token count for backends that can't tokenize.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Initial prompt from the custom vocabulary followed by the end of the previous dictation,
+/// within whisper's prompt budget as measured by `count_tokens`. The vocabulary is kept whole
+/// where it fits; the previous text fills what is left, cut at a word so the prompt ends where
+/// the new speech begins. Whisper drops the start of an over-long prompt, which would be the
+/// vocabulary, so the budget has to be met here.
+pub fn prompt(custom_vocab: &str, previous: &str, count_tokens: impl Fn(&str) -> usize) -> String {
+    let fits = |text: &str| count_tokens(text) <= PROMPT_TOKEN_BUDGET;
+    let vocab = custom_vocab.trim();
+    let vocab = if fits(vocab) {
+        vocab
+    } else {
+        // The longest run of whole words from the start that fits.
+        let ends: Vec<usize> = word_ends(vocab);
+        let fitting = ends.partition_point(|&end| fits(&vocab[..end]));
+        fitting
+            .checked_sub(1)
+            .map_or("", |index| &vocab[..ends[index]])
+    };
+
+    let previous = previous.trim();
+    let join = |tail: &str| match (vocab.is_empty(), tail.is_empty()) {
+        (_, true) => vocab.to_string(),
+        (true, false) => tail.to_string(),
+        (false, false) => format!("{vocab} {tail}"),
+    };
+    // The longest run of whole words from the end that still fits after the vocabulary.
+    let starts = word_starts(previous);
+    let first = starts.partition_point(|&start| !fits(&join(&previous[start..])));
+    let tail = starts.get(first).map_or("", |&start| &previous[start..]);
+    join(tail).replace('\0', " ")
+}
+
+/// Byte offsets where the words of `text` end, in order.
+fn word_ends(text: &str) -> Vec<usize> {
+    let mut ends: Vec<usize> = text
+        .char_indices()
+        .zip(text.chars().skip(1))
+        .filter(|((_, ch), next)| !ch.is_whitespace() && next.is_whitespace())
+        .map(|((index, ch), _)| index + ch.len_utf8())
+        .collect();
+    if !text.is_empty() {
+        ends.push(text.len());
+    }
+    ends
+}
+
+/// Byte offsets where the words of `text` start, in order.
+fn word_starts(text: &str) -> Vec<usize> {
+    let mut previous_space = true;
+    text.char_indices()
+        .filter(|(_, ch)| {
+            let start = previous_space && !ch.is_whitespace();
+            previous_space = ch.is_whitespace();
+            start
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn previous(ended_at_ms: i64, focus_window: Option<&str>) -> PreviousDictation {
+        PreviousDictation {
+            text: "Hi Priya,".to_string(),
+            ended_at_ms,
+            focus_window: focus_window.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn continues_soon_after_or_in_the_same_window() {
+        let last = previous(10_000, Some("mail"));
+        assert!(continues(&last, 40_000, Some("editor"), 30));
+        assert!(!continues(&last, 40_001, Some("editor"), 30));
+        assert!(continues(&last, 900_000, Some("mail"), 30));
+        assert!(!continues(&previous(10_000, None), 900_000, None, 30));
+    }
+
+    #[test]
+    fn prompt_keeps_the_vocabulary_and_the_end_of_the_previous_text() {
+        assert_eq!(
+            prompt("Kubernetes, PostgREST", "", estimate_tokens),
+            "Kubernetes, PostgREST"
+        );
+        assert_eq!(
+            prompt("", "Hi Priya, thanks for the notes.", estimate_tokens),
+            "Hi Priya, thanks for the notes."
+        );
+
+        let long = "word ".repeat(400) + "and finally Priya signed off.";
+        let merged = prompt("Kubernetes", &long, estimate_tokens);
+        assert!(merged.starts_with("Kubernetes word"));
+        assert!(merged.ends_with("and finally Priya signed off."));
+        assert!(estimate_tokens(&merged) <= PROMPT_TOKEN_BUDGET);
+
+        let vocab = "Kubernetes ".repeat(100);
+        let merged = prompt(&vocab, "Hi Priya", estimate_tokens);
+        assert!(merged.starts_with("Kubernetes"));
+        assert!(estimate_tokens(&merged) <= PROMPT_TOKEN_BUDGET);
+    }
+
+    #[test]
+    fn prompt_budget_follows_the_tokenizer() {
+        // Roughly how whisper splits CJK text: a token or more per character.
+        let per_char = |text: &str| text.chars().filter(|ch| !ch.is_whitespace()).count();
+        let previous = "前回の 会議で 決まった ことを ".repeat(20) + "最後に 確認します";
+        let merged = prompt("東京都 渋谷区", &previous, per_char);
+        assert!(merged.starts_with("東京都 渋谷区 "));
+        assert!(merged.ends_with("最後に 確認します"));
+        assert!(per_char(&merged) <= PROMPT_TOKEN_BUDGET);
+        assert!(merged.chars().count() < PROMPT_TOKEN_BUDGET * CHARS_PER_TOKEN);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::settings::Settings;
+use crate::types::DictationCommand;
+
+/// Punctuation attached to the previous word, replacing any punctuation whisper put there.
+pub const ACTION_INSERT: &str = "insert";
+/// An opening mark (quote, bracket) attached to the next word.
+pub const ACTION_OPEN: &str = "open";
+pub const ACTION_NEW_LINE: &str = "new_line";
+pub const ACTION_NEW_PARAGRAPH: &str = "new_paragraph";
+/// Drop the sentence dictated before the command.
+pub const ACTION_SCRATCH: &str = "scratch_that";
+pub const ACTION_CAPS_ON: &str = "caps_on";
+pub const ACTION_CAPS_OFF: &str = "caps_off";
+pub const ACTIONS: &[&str] = &[
+    ACTION_INSERT,
+    ACTION_OPEN,
+    ACTION_NEW_LINE,
+    ACTION_NEW_PARAGRAPH,
+    ACTION_SCRATCH,
+    ACTION_CAPS_ON,
+    ACTION_CAPS_OFF,
+];
+
+/// Command table seeded into a new database.
+const DEFAULT_COMMANDS: &[(&str, &str, &str, &str)] = &[
+    ("en", "new line", ACTION_NEW_LINE, ""),
+    ("en", "new paragraph", ACTION_NEW_PARAGRAPH, ""),
+    ("en", "comma", ACTION_INSERT, ","),
+    ("en", "period", ACTION_INSERT, "."),
+    ("en", "full stop", ACTION_INSERT, "."),
+    ("en", "question mark", ACTION_INSERT, "?"),
+    ("en", "exclamation mark", ACTION_INSERT, "!"),
+    ("en", "exclamation point", ACTION_INSERT, "!"),
+    ("en", "colon", ACTION_INSERT, ":"),
+    ("en", "semicolon", ACTION_INSERT, ";"),
+    ("en", "open quote", ACTION_OPEN, "\""),
+    ("en", "close quote", ACTION_INSERT, "\""),
+    ("en", "end quote", ACTION_INSERT, "\""),
+    ("en", "open paren", ACTION_OPEN, "("),
+    ("en", "close paren", ACTION_INSERT, ")"),
+    ("en", "scratch that", ACTION_SCRATCH, ""),
+    ("en", "all caps", ACTION_CAPS_ON, ""),
+    ("en", "end caps", ACTION_CAPS_OFF, ""),
+];
+
+/// Punctuation whisper adds around a spoken command, replaced by an inserted mark.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?'];
+const SENTENCE_END: &[char] = &['.', '!', '?'];
+
+pub fn default_commands() -> Vec<DictationCommand> {
+    DEFAULT_COMMANDS
+        .iter()
+        .map(|(language, phrase, action, value)| DictationCommand {
+            id: String::new(),
+            language: language.to_string(),
+            phrase: phrase.to_string(),
+            action: action.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+/// Whether spoken commands are interpreted for text in `language`.
+pub fn enabled(settings: &Settings, language: &str) -> bool {
+    settings
+        .transcription
+        .dictation_languages
+        .iter()
+        .any(|enabled| enabled.eq_ignore_ascii_case(language))
+}
+
+/// Interpret spoken commands in `text`. Phrases match whole words, ignoring case and the
+/// punctuation whisper puts around them; the longest phrase wins.
+pub fn apply(text: &str, commands: &[DictationCommand]) -> String {
+    let mut phrases: Vec<(Vec<String>, &DictationCommand)> = commands
+        .iter()
+        .map(|command| {
+            let words = command
+                .phrase
+                .split_whitespace()
+                .map(normalize_word)
+                .filter(|word| !word.is_empty())
+                .collect();
+            (words, command)
+        })
+        .filter(|(words, _): &(Vec<String>, _)| !words.is_empty())
+        .collect();
+    phrases.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = tokens.iter().map(|token| normalize_word(token)).collect();
+    let mut writer = Writer::default();
+    let mut index = 0;
+    while index < tokens.len() {
+        match phrases
+            .iter()
+            .find(|(words, _)| keys[index..].starts_with(words))
+        {
+            Some((words, command)) => {
+                writer.command(command);
+                index += words.len();
+            }
+            None => {
+                writer.word(tokens[index]);
+                index += 1;
+            }
+        }
+    }
+    writer.out.trim_end_matches(' ').to_string()
+}
+
+#[derive(Default)]
+struct Writer {
+    out: String,
+    /// No space before the next word (after an opening mark).
+    glue: bool,
+    capitalize: bool,
+    caps: bool,
+}
+
+impl Writer {
+    fn word(&mut self, word: &str) {
+        self.space();
+        let word = if self.caps {
+            word.to_uppercase()
+        } else {
+            word.to_string()
+        };
+        if self.capitalize {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                self.out.extend(first.to_uppercase());
+                self.out.push_str(chars.as_str());
+            }
+        } else {
+            self.out.push_str(&word);
+        }
+        self.capitalize = false;
+    }
+
+    fn space(&mut self) {
+        if !self.glue && !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push(' ');
+        }
+        self.glue = false;
+    }
+
+    fn command(&mut self, command: &DictationCommand) {
+        match command.action.as_str() {
+            ACTION_INSERT => {
+                let kept = self
+                    .out
+                    .trim_end()
+                    .trim_end_matches(TRAILING_PUNCTUATION)
+                    .len();
+                self.out.truncate(kept);
+                self.out.push_str(&command.value);
+                self.capitalize = command.value.ends_with(SENTENCE_END);
+                self.glue = false;
+            }
+            ACTION_OPEN => {
+                self.space();
+                self.out.push_str(&command.value);
+                self.glue = true;
+            }
+            ACTION_NEW_LINE => {
+                self.trim_spaces();
+                self.out.push('\n');
+            }
+            ACTION_NEW_PARAGRAPH => {
+                self.trim_spaces();
+                self.out.push_str("\n\n");
+                self.capitalize = true;
+            }
+            ACTION_SCRATCH => self.scratch(),
+            ACTION_CAPS_ON => self.caps = true,
+            ACTION_CAPS_OFF => self.caps = false,
+            _ => {}
+        }
+    }
+
+    /// Remove everything back to the end of the sentence before the last one.
+    fn scratch(&mut self) {
+        let sentence = self.out.trim_end().trim_end_matches(TRAILING_PUNCTUATION);
+        let start = sentence
+            .rfind(|ch: char| SENTENCE_END.contains(&ch) || ch == '\n')
+            .map_or(0, |index| index + 1);
+        self.out.truncate(start);
+        self.trim_spaces();
+        self.glue = false;
+    }
+
+    fn trim_spaces(&mut self) {
+        let kept = self.out.trim_end_matches(' ').len();
+        self.out.truncate(kept);
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric() || *ch == '\'')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str) -> String {
+        apply(text, &default_commands())
+    }
+
+    #[test]
+    fn inserts_punctuation_and_line_breaks() {
+        assert_eq!(
+            run("Dear Sam, comma, new paragraph. Thanks for the draft period see you Monday"),
+            "Dear Sam,\n\nThanks for the draft. See you Monday"
+        );
+        assert_eq!(run("One new line two"), "One\ntwo");
+        assert_eq!(
+            run("She said, open quote, hello there. Close quote."),
+            "She said, \"hello there\""
+        );
+    }
+
+    #[test]
+    fn scratch_that_drops_the_previous_sentence() {
+        assert_eq!(
+            run("First point. I like cats. Scratch that. I like dogs."),
+            "First point. I like dogs."
+        );
+        assert_eq!(run("I like cats, scratch that, dogs"), "dogs");
+    }
+
+    #[test]
+    fn all_caps_until_end_caps() {
+        assert_eq!(
+            run("This is all caps very important end caps for everyone."),
+            "This is VERY IMPORTANT for everyone."
+        );
+    }
+
+    #[test]
+    fn language_must_be_enabled() {
+        let mut settings = Settings::default();
+        settings.transcription.dictation_languages = vec!["en".to_string()];
+        assert!(enabled(&settings, "EN"));
+        assert!(!enabled(&settings, "de"));
+    }
+}
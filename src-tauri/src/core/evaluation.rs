@@ -0,0 +1,179 @@
+use std::path::{Path, PathBuf};
+
+/// Edit operations turning a reference into a hypothesis, as counted for WER and CER.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorCounts {
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    /// Reference length the rate is relative to.
+    pub reference: usize,
+}
+
+impl ErrorCounts {
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    /// Errors per reference unit; above 1 when the hypothesis adds more than the reference has.
+    pub fn rate(&self) -> f32 {
+        if self.reference == 0 {
+            return if self.insertions == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors() as f32 / self.reference as f32
+    }
+
+    pub fn add(&mut self, other: ErrorCounts) {
+        self.substitutions += other.substitutions;
+        self.deletions += other.deletions;
+        self.insertions += other.insertions;
+        self.reference += other.reference;
+    }
+}
+
+/// Text reduced to what WER should compare: lowercase words without punctuation. Hyphens and
+/// slashes separate words; apostrophes inside words are kept ("don't").
+pub fn normalize(text: &str) -> Vec<String> {
+    text.split(|ch: char| ch.is_whitespace() || matches!(ch, '-' | '–' | '—' | '/'))
+        .map(|word| {
+            word.chars()
+                .filter(|ch| ch.is_alphanumeric() || matches!(ch, '\'' | '’'))
+                .map(|ch| if ch == '’' { '\'' } else { ch })
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Word-level errors between normalized texts.
+pub fn word_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    align(&normalize(reference), &normalize(hypothesis))
+}
+
+/// Character-level errors between normalized texts, counting the single spaces between words.
+pub fn char_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let chars = |text: &str| normalize(text).join(" ").chars().collect::<Vec<_>>();
+    align(&chars(reference), &chars(hypothesis))
+}
+
+/// Minimum edit distance alignment, broken down by operation. Among equally short alignments,
+/// substitutions are preferred over a deletion plus an insertion.
+pub fn align<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> ErrorCounts {
+    let columns = hypothesis.len() + 1;
+    // (cost, substitutions, deletions, insertions) per cell, one row at a time.
+    let mut previous: Vec<(usize, usize, usize, usize)> =
+        (0..columns).map(|j| (j, 0, 0, j)).collect();
+    for (i, expected) in reference.iter().enumerate() {
+        let mut current = vec![(i + 1, 0, i + 1, 0); columns];
+        for (j, actual) in hypothesis.iter().enumerate() {
+            let diagonal = previous[j];
+            let substitute = if expected == actual {
+                diagonal
+            } else {
+                (diagonal.0 + 1, diagonal.1 + 1, diagonal.2, diagonal.3)
+            };
+            let above = previous[j + 1];
+            let delete = (above.0 + 1, above.1, above.2 + 1, above.3);
+            let left = current[j];
+            let insert = (left.0 + 1, left.1, left.2, left.3 + 1);
+            current[j + 1] = [substitute, delete, insert]
+                .into_iter()
+                .min_by_key(|cell| cell.0)
+                .unwrap_or(substitute);
+        }
+        previous = current;
+    }
+    let (_, substitutions, deletions, insertions) = previous[hypothesis.len()];
+    ErrorCounts {
+        substitutions,
+        deletions,
+        insertions,
+        reference: reference.len(),
+    }
+}
+
+/// Pair each audio file with the reference transcript next to it (same name, `.txt`).
+pub fn reference_pairs(paths: &[String]) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    if paths.is_empty() {
+        return Err("Choose at least one audio file with a reference transcript".to_string());
+    }
+    paths
+        .iter()
+        .map(|path| {
+            let audio = PathBuf::from(path);
+            let reference = audio.with_extension("txt");
+            if reference.is_file() {
+                Ok((audio, reference))
+            } else {
+                Err(format!(
+                    "No reference transcript for {}: expected {}",
+                    file_name(&audio),
+                    file_name(&reference)
+                ))
+            }
+        })
+        .collect()
+}
+
+pub fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_and_punctuation() {
+        assert_eq!(
+            normalize("Hello, World! It's a well-known 'test'."),
+            vec!["hello", "world", "it's", "a", "well", "known", "test"]
+        );
+        assert_eq!(normalize("Don’t  stop"), vec!["don't", "stop"]);
+    }
+
+    #[test]
+    fn counts_each_kind_of_error() {
+        let counts = word_errors(
+            "the quick brown fox jumps over the dog",
+            "The quick, red fox jumps over the lazy dog.",
+        );
+        assert_eq!(counts.substitutions, 1);
+        assert_eq!(counts.insertions, 1);
+        assert_eq!(counts.deletions, 0);
+        assert_eq!(counts.reference, 8);
+        assert_eq!(counts.rate(), 0.25);
+
+        let counts = word_errors("one two three", "one three");
+        assert_eq!((counts.deletions, counts.errors()), (1, 1));
+        assert_eq!(word_errors("", "").rate(), 0.0);
+    }
+
+    #[test]
+    fn character_errors_include_spaces() {
+        let counts = char_errors("post grest", "PostgREST");
+        assert_eq!(counts.reference, 10);
+        assert_eq!(counts.errors(), 1);
+        assert_eq!(counts.deletions, 1);
+    }
+
+    #[test]
+    fn references_must_sit_next_to_the_audio() {
+        let dir = std::env::temp_dir().join(format!("whispr-eval-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let audio = dir.join("meeting.wav");
+        std::fs::write(dir.join("meeting.txt"), "hello").expect("reference");
+
+        let pairs = reference_pairs(&[audio.to_string_lossy().to_string()]).expect("pairs");
+        assert_eq!(pairs, vec![(audio.clone(), dir.join("meeting.txt"))]);
+        let missing = dir.join("other.mp3").to_string_lossy().to_string();
+        assert!(reference_pairs(&[missing]).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
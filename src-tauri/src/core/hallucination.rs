@@ -0,0 +1,266 @@
+use crate::types::{FilteredSegment, TranscriptSegment, TranscriptWord};
+
+pub const REASON_NO_SPEECH: &str = "no_speech";
+pub const REASON_KNOWN_PHRASE: &str = "known_phrase";
+pub const REASON_REPETITION: &str = "repetition";
+
+/// Caption credits and video outros whisper learned from subtitled uploads. Matched against the
+/// whole segment after normalisation, so they never remove part of a real sentence.
+const KNOWN_PHRASES: &[&str] = &[
+    "thanks for watching",
+    "thank you for watching",
+    "thank you so much for watching",
+    "thanks for watching see you next time",
+    "please subscribe",
+    "please subscribe to my channel",
+    "like and subscribe",
+    "dont forget to like and subscribe",
+    "see you in the next video",
+    "subtitles by the amaraorg community",
+    "subtitles by",
+    "transcribed by",
+    "translated by",
+];
+/// Things people really do say on their own; only dropped when whisper also doubts there was
+/// any speech.
+const AMBIGUOUS_PHRASES: &[&str] = &["thank you", "thanks", "you", "bye", "bye bye", "so"];
+const AMBIGUOUS_NO_SPEECH: f32 = 0.3;
+/// Identical consecutive segments beyond the first are a loop once there are this many.
+const SEGMENT_REPEATS: usize = 3;
+/// A segment is a loop when one n-gram (up to `MAX_NGRAM` words) repeats back to back at least
+/// `NGRAM_REPEATS` times and the repeats make up most of the segment.
+const MAX_NGRAM: usize = 8;
+const NGRAM_REPEATS: usize = 4;
+const MIN_LOOP_WORDS: usize = 8;
+
+/// A decoded segment with the words inside it and whisper's confidence estimates.
+pub struct Candidate {
+    pub segment: TranscriptSegment,
+    pub words: Vec<TranscriptWord>,
+    /// Whisper's probability that the window held no speech at all.
+    pub no_speech: f32,
+    /// Mean log probability of the segment's text tokens.
+    pub avg_logprob: f32,
+}
+
+/// Split whisper output into segments worth keeping and likely hallucinations.
+///
+/// As in whisper's own decoder, a high no-speech probability only drops a segment when its text
+/// was also decoded with low confidence (`avg_logprob` below `logprob_threshold`).
+pub fn filter(
+    candidates: Vec<Candidate>,
+    no_speech_threshold: f32,
+    logprob_threshold: f32,
+) -> (Vec<Candidate>, Vec<FilteredSegment>) {
+    let normalized: Vec<String> = candidates
+        .iter()
+        .map(|candidate| normalize(&candidate.segment.text))
+        .collect();
+    let repeated = repeated_segments(&normalized);
+
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    for (index, candidate) in candidates.into_iter().enumerate() {
+        let text = &normalized[index];
+        let reason = if candidate.no_speech >= no_speech_threshold
+            && candidate.avg_logprob < logprob_threshold
+        {
+            Some(REASON_NO_SPEECH)
+        } else if is_known_phrase(&candidate.segment.text, text, candidate.no_speech) {
+            Some(REASON_KNOWN_PHRASE)
+        } else if repeated[index] || has_repetition_loop(text) {
+            Some(REASON_REPETITION)
+        } else {
+            None
+        };
+
+        match reason {
+            Some(reason) => removed.push(FilteredSegment {
+                start_ms: candidate.segment.start_ms,
+                end_ms: candidate.segment.end_ms,
+                text: candidate.segment.text,
+                reason: reason.to_string(),
+            }),
+            None => kept.push(candidate),
+        }
+    }
+
+    (kept, removed)
+}
+
+fn is_known_phrase(raw: &str, normalized: &str, no_speech: f32) -> bool {
+    // "[Music]", "(applause)", "♪♪" and friends: annotations, not speech.
+    if normalized.is_empty() || strip_annotations(raw).trim().is_empty() {
+        return true;
+    }
+    KNOWN_PHRASES.contains(&normalized)
+        || (no_speech >= AMBIGUOUS_NO_SPEECH && AMBIGUOUS_PHRASES.contains(&normalized))
+}
+
+/// Mark every segment after the first in a run of `SEGMENT_REPEATS` or more identical ones.
+fn repeated_segments(normalized: &[String]) -> Vec<bool> {
+    let mut repeated = vec![false; normalized.len()];
+    let mut start = 0;
+    while start < normalized.len() {
+        let mut end = start + 1;
+        while end < normalized.len() && normalized[end] == normalized[start] {
+            end += 1;
+        }
+        if end - start >= SEGMENT_REPEATS && !normalized[start].is_empty() {
+            repeated[start + 1..end].fill(true);
+        }
+        start = end;
+    }
+    repeated
+}
+
+fn has_repetition_loop(normalized: &str) -> bool {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    for n in 1..=MAX_NGRAM.min(words.len() / NGRAM_REPEATS) {
+        for start in 0..words.len().saturating_sub(n * NGRAM_REPEATS - 1) {
+            let gram = &words[start..start + n];
+            let mut repeats = 1;
+            while start + (repeats + 1) * n <= words.len()
+                && &words[start + repeats * n..start + (repeats + 1) * n] == gram
+            {
+                repeats += 1;
+            }
+            let span = repeats * n;
+            if repeats >= NGRAM_REPEATS && span >= MIN_LOOP_WORDS && span * 2 >= words.len() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn strip_annotations(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut depth = 0_usize;
+    for ch in text.chars() {
+        match ch {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            '♪' | '♫' => {}
+            _ if depth == 0 => out.push(ch),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Lowercase words with punctuation removed, separated by single spaces.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|ch| ch.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(text: &str, start_ms: i64, no_speech: f32) -> Candidate {
+        scored(text, start_ms, no_speech, -0.2)
+    }
+
+    fn scored(text: &str, start_ms: i64, no_speech: f32, avg_logprob: f32) -> Candidate {
+        Candidate {
+            segment: TranscriptSegment {
+                start_ms,
+                end_ms: start_ms + 1_000,
+                text: text.to_string(),
+                speaker: None,
+            },
+            words: Vec::new(),
+            no_speech,
+            avg_logprob,
+        }
+    }
+
+    fn run(candidates: Vec<Candidate>) -> (Vec<String>, Vec<(String, String)>) {
+        let (kept, removed) = filter(candidates, 0.6, -1.0);
+        (
+            kept.into_iter().map(|item| item.segment.text).collect(),
+            removed
+                .into_iter()
+                .map(|item| (item.text, item.reason))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn drops_known_outros_and_annotations() {
+        let (kept, removed) = run(vec![
+            candidate("Send the report by Friday.", 0, 0.01),
+            candidate("Thanks for watching!", 1_000, 0.05),
+            candidate("[Music]", 2_000, 0.05),
+            candidate("♪ ♪", 3_000, 0.05),
+        ]);
+        assert_eq!(kept, vec!["Send the report by Friday."]);
+        assert!(removed
+            .iter()
+            .all(|(_, reason)| reason == REASON_KNOWN_PHRASE));
+        assert_eq!(removed.len(), 3);
+    }
+
+    #[test]
+    fn ambiguous_phrases_need_no_speech_evidence() {
+        let (kept, removed) = run(vec![
+            candidate("Thank you.", 0, 0.02),
+            candidate("Thank you.", 1_000, 0.45),
+        ]);
+        assert_eq!(kept, vec!["Thank you."]);
+        assert_eq!(removed[0].1, REASON_KNOWN_PHRASE);
+    }
+
+    #[test]
+    fn drops_high_no_speech_segments() {
+        let (kept, removed) = run(vec![
+            scored("So anyway.", 0, 0.9, -1.4),
+            scored("Confident speech.", 1_000, 0.9, -0.3),
+        ]);
+        assert_eq!(kept, vec!["Confident speech."]);
+        assert_eq!(removed[0].1, REASON_NO_SPEECH);
+    }
+
+    #[test]
+    fn drops_repeated_segments_after_the_first() {
+        let (kept, removed) = run(vec![
+            candidate("I'm going to go.", 0, 0.01),
+            candidate("I'm going to go.", 1_000, 0.01),
+            candidate("I'm going to go!", 2_000, 0.01),
+            candidate("Done.", 3_000, 0.01),
+        ]);
+        assert_eq!(kept, vec!["I'm going to go.", "Done."]);
+        assert_eq!(removed.len(), 2);
+        assert!(removed
+            .iter()
+            .all(|(_, reason)| reason == REASON_REPETITION));
+
+        // Saying something twice is fine.
+        let (kept, _) = run(vec![
+            candidate("Hello.", 0, 0.01),
+            candidate("Hello.", 1_000, 0.01),
+        ]);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn detects_ngram_loops_within_a_segment() {
+        assert!(has_repetition_loop(&normalize(
+            "and then we and then we and then we and then we and then we"
+        )));
+        assert!(!has_repetition_loop(&normalize("no no no no")));
+        assert!(!has_repetition_loop(&normalize(
+            "we need the draft, the final draft, and the draft notes before the draft review"
+        )));
+    }
+}
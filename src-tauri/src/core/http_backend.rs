@@ -0,0 +1,518 @@
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use hound::{SampleFormat, WavSpec, WavWriter};
+use reqwest::blocking::Client;
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::continuation;
+use crate::core::hallucination::Candidate;
+use crate::core::transcription::{
+    self, TranscriptionBackend, TranscriptionOutput, AUTO_LANGUAGE, TARGET_SAMPLE_RATE,
+    TRANSCRIPTION_CANCELLED,
+};
+use crate::settings::Settings;
+use crate::types::{TranscriptSegment, TranscriptWord};
+
+const REQUEST_TIMEOUT_SECS: u64 = 10 * 60;
+/// OpenAI-compatible servers need a model name; this is the one they all accept.
+const OPENAI_DEFAULT_MODEL: &str = "whisper-1";
+
+/// Sends audio to a whisper.cpp `server` or an OpenAI-compatible transcription endpoint.
+///
+/// The request has no way to interrupt the remote decode, so cancellation only takes effect
+/// once the response arrives.
+pub struct HttpBackend;
+
+impl TranscriptionBackend for HttpBackend {
+    fn transcribe(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+        previous: &str,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<TranscriptionOutput, String> {
+        let cancelled = || cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+        if cancelled() {
+            return Err(TRANSCRIPTION_CANCELLED.to_string());
+        }
+        let output = request(settings, samples, previous)?;
+        if cancelled() {
+            return Err(TRANSCRIPTION_CANCELLED.to_string());
+        }
+        Ok(output)
+    }
+
+    fn transcribe_preview(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+    ) -> Result<TranscriptionOutput, String> {
+        request(settings, samples, "")
+    }
+
+    fn warm_up(&self, settings: &Settings) -> Result<(), String> {
+        endpoint(settings).map(|_| ())
+    }
+}
+
+/// Request format, picked from the endpoint path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Api {
+    /// whisper.cpp `server` (`/inference`).
+    WhisperCpp,
+    /// OpenAI `/v1/audio/transcriptions` and servers that mimic it.
+    OpenAi,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseResponse {
+    #[serde(default)]
+    text: String,
+    /// Full language name ("english") in both APIs.
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<ResponseSegment>,
+    /// OpenAI reports words for the whole file rather than per segment.
+    #[serde(default)]
+    words: Vec<ResponseWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    /// whisper.cpp reports words per segment.
+    #[serde(default)]
+    words: Vec<ResponseWord>,
+    #[serde(default)]
+    no_speech_prob: f32,
+    #[serde(default)]
+    avg_logprob: f32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseWord {
+    word: String,
+    start: f64,
+    end: f64,
+    #[serde(default)]
+    probability: Option<f32>,
+}
+
+fn request(
+    settings: &Settings,
+    samples: &[f32],
+    previous: &str,
+) -> Result<TranscriptionOutput, String> {
+    let (mut url, api) = endpoint(settings)?;
+    let config = &settings.transcription;
+    let translate = config.translate;
+    let language = config.language.trim();
+    let auto_language = language.is_empty() || language.eq_ignore_ascii_case(AUTO_LANGUAGE);
+
+    let mut form = Multipart::new();
+    form.file("file", "audio.wav", "audio/wav", &encode_wav(samples)?);
+    form.text("response_format", "verbose_json");
+    form.text("temperature", &config.temperature.max(0.0).to_string());
+    let prompt = continuation::prompt(
+        &config.custom_vocab,
+        previous,
+        continuation::estimate_tokens,
+    );
+    if !prompt.is_empty() {
+        form.text("prompt", &prompt);
+    }
+    let model = config.server_model.trim();
+    match api {
+        Api::WhisperCpp => {
+            form.text(
+                "temperature_inc",
+                &config.temperature_increment.max(0.0).to_string(),
+            );
+            form.text(
+                "language",
+                if auto_language {
+                    AUTO_LANGUAGE
+                } else {
+                    language
+                },
+            );
+            form.text("translate", if translate { "true" } else { "false" });
+            if !model.is_empty() {
+                form.text("model", model);
+            }
+        }
+        Api::OpenAi => {
+            form.text(
+                "model",
+                if model.is_empty() {
+                    OPENAI_DEFAULT_MODEL
+                } else {
+                    model
+                },
+            );
+            form.text("timestamp_granularities[]", "segment");
+            form.text("timestamp_granularities[]", "word");
+            if translate {
+                // Translation is its own endpoint and always targets English.
+                let path = url
+                    .path()
+                    .replace("/audio/transcriptions", "/audio/translations");
+                url.set_path(&path);
+            } else if !auto_language {
+                form.text("language", language);
+            }
+        }
+    }
+
+    let (content_type, body) = form.finish();
+    let response = client()?
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(body)
+        .send()
+        .map_err(|err| format!("Transcription server request failed: {err}"))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|err| format!("Transcription server response unreadable: {err}"))?;
+    if let Some(message) = error_message(&body) {
+        return Err(format!("Transcription server error ({status}): {message}"));
+    }
+    if !status.is_success() {
+        return Err(format!("Transcription server returned {status}"));
+    }
+    let response: VerboseResponse = serde_json::from_str(&body)
+        .map_err(|err| format!("Unexpected transcription server response: {err}"))?;
+
+    let duration_ms = samples.len() as i64 * 1000 / TARGET_SAMPLE_RATE as i64;
+    Ok(into_output(settings, response, duration_ms, translate))
+}
+
+fn endpoint(settings: &Settings) -> Result<(Url, Api), String> {
+    let raw = settings.transcription.server_url.trim();
+    let url = Url::parse(raw).map_err(|err| format!("Invalid server URL \"{raw}\": {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Server URL must use http or https: {raw}"));
+    }
+    let api = if url
+        .path()
+        .trim_end_matches('/')
+        .ends_with("/audio/transcriptions")
+    {
+        Api::OpenAi
+    } else {
+        Api::WhisperCpp
+    };
+    Ok((url, api))
+}
+
+fn client() -> Result<&'static Client, String> {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    // The server is expected on the local network, so system proxies are skipped.
+    let client = Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .no_proxy()
+        .build()
+        .map_err(|err| err.to_string())?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+/// The `error` field both APIs use for failures: a string (whisper.cpp) or `{ message }` (OpenAI).
+fn error_message(body: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(body).ok()?;
+    match value.get("error")? {
+        Value::String(message) => Some(message.clone()),
+        error => Some(
+            error
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string()),
+        ),
+    }
+}
+
+fn into_output(
+    settings: &Settings,
+    response: VerboseResponse,
+    duration_ms: i64,
+    translated: bool,
+) -> TranscriptionOutput {
+    let VerboseResponse {
+        text,
+        language,
+        mut segments,
+        words,
+    } = response;
+    // Plain `json` responses (or servers that ignore verbose_json) only carry the text.
+    if segments.is_empty() && !text.trim().is_empty() {
+        segments.push(ResponseSegment {
+            start: 0.0,
+            end: duration_ms as f64 / 1000.0,
+            text,
+            words: Vec::new(),
+            no_speech_prob: 0.0,
+            avg_logprob: 0.0,
+        });
+    }
+
+    let mut loose_words = words.into_iter().peekable();
+    let last = segments.len().saturating_sub(1);
+    let mut candidates = Vec::new();
+    for (index, segment) in segments.into_iter().enumerate() {
+        let start_ms = seconds_to_ms(segment.start);
+        let end_ms = seconds_to_ms(segment.end);
+        let mut segment_words = segment.words;
+        // File-level words go to the segment their midpoint falls in; the last takes the rest.
+        while let Some(word) = loose_words
+            .next_if(|word| index == last || seconds_to_ms((word.start + word.end) / 2.0) < end_ms)
+        {
+            segment_words.push(word);
+        }
+
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        candidates.push(Candidate {
+            segment: TranscriptSegment {
+                start_ms,
+                end_ms,
+                text: text.to_string(),
+                speaker: None,
+            },
+            words: segment_words
+                .into_iter()
+                .filter(|word| !word.word.trim().is_empty())
+                .map(|word| TranscriptWord {
+                    start_ms: seconds_to_ms(word.start),
+                    end_ms: seconds_to_ms(word.end),
+                    text: word.word.trim().to_string(),
+                    confidence: word.probability.unwrap_or(1.0),
+                })
+                .collect(),
+            no_speech: segment.no_speech_prob,
+            avg_logprob: segment.avg_logprob,
+        });
+    }
+
+    let language = language.as_deref().and_then(language_code);
+    transcription::build_output(settings, candidates, language, Vec::new(), translated)
+}
+
+/// Map the language name servers report ("english") to whisper's code ("en").
+fn language_code(name: &str) -> Option<String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    whisper_rs::get_lang_id(&name)
+        .and_then(whisper_rs::get_lang_str)
+        .map(str::to_string)
+}
+
+fn seconds_to_ms(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+fn encode_wav(samples: &[f32]) -> Result<Vec<u8>, String> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate: TARGET_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: SampleFormat::Int,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = WavWriter::new(&mut cursor, spec).map_err(|err| err.to_string())?;
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        writer.write_sample(value).map_err(|err| err.to_string())?;
+    }
+    writer.finalize().map_err(|err| err.to_string())?;
+    Ok(cursor.into_inner())
+}
+
+/// Minimal `multipart/form-data` body builder.
+struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    fn new() -> Self {
+        Self {
+            boundary: format!("whispr-{}", Uuid::new_v4().simple()),
+            body: Vec::new(),
+        }
+    }
+
+    fn text(&mut self, name: &str, value: &str) {
+        self.header(&format!("Content-Disposition: form-data; name=\"{name}\""));
+        self.body.extend_from_slice(value.as_bytes());
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    fn file(&mut self, name: &str, filename: &str, content_type: &str, data: &[u8]) {
+        self.header(&format!(
+            "Content-Disposition: form-data; name=\"{name}\"; filename=\"{filename}\"\r\n\
+             Content-Type: {content_type}"
+        ));
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    fn header(&mut self, headers: &str) {
+        self.body
+            .extend_from_slice(format!("--{}\r\n{headers}\r\n\r\n", self.boundary).as_bytes());
+    }
+
+    /// The `Content-Type` header value and the finished body.
+    fn finish(mut self) -> (String, Vec<u8>) {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        (
+            format!("multipart/form-data; boundary={}", self.boundary),
+            self.body,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serve one request with `response` and hand back the request line and body.
+    fn stand_in_server(response: &'static str) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).expect("request line");
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("header");
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().expect("length");
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).expect("body");
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+                 Connection: close\r\n\r\n{response}",
+                response.len()
+            )
+            .expect("respond");
+            let _ = tx.send((
+                request_line.trim().to_string(),
+                String::from_utf8_lossy(&body).to_string(),
+            ));
+        });
+        (format!("http://{addr}"), rx)
+    }
+
+    fn settings(server_url: String) -> Settings {
+        let mut settings = Settings::default();
+        settings.transcription.backend = crate::settings::TRANSCRIPTION_BACKEND_HTTP.to_string();
+        settings.transcription.server_url = server_url;
+        settings
+    }
+
+    #[test]
+    fn whisper_cpp_server_round_trip() {
+        let (base, requests) = stand_in_server(
+            r#"{"text":" Hello there. Thanks for watching!","segments":[
+                {"start":0.0,"end":1.2,"text":" Hello there.","no_speech_prob":0.01,"avg_logprob":-0.2,
+                 "words":[{"word":" Hello","start":0.0,"end":0.5,"probability":0.9},
+                          {"word":" there.","start":0.5,"end":1.2,"probability":0.8}]},
+                {"start":1.2,"end":2.0,"text":" Thanks for watching!","no_speech_prob":0.02,"avg_logprob":-0.3}
+            ]}"#,
+        );
+        let settings = settings(format!("{base}/inference"));
+
+        let output = HttpBackend
+            .transcribe(&settings, &vec![0.0; 32_000], "", None)
+            .expect("transcribe");
+        assert_eq!(output.text, "Hello there.");
+        assert_eq!(output.words.len(), 2);
+        assert_eq!(
+            (output.words[1].start_ms, output.words[1].end_ms),
+            (500, 1_200)
+        );
+        assert_eq!(output.filtered.len(), 1);
+
+        let (request_line, body) = requests.recv().expect("request");
+        assert!(request_line.starts_with("POST /inference "));
+        assert!(body.contains("name=\"response_format\"\r\n\r\nverbose_json"));
+        assert!(body.contains("name=\"language\"\r\n\r\nen"));
+        assert!(body.contains("filename=\"audio.wav\""));
+    }
+
+    #[test]
+    fn openai_words_are_assigned_to_segments() {
+        let (base, requests) = stand_in_server(
+            r#"{"text":"One two",
+                "segments":[{"start":0.0,"end":1.0,"text":"One"},{"start":1.0,"end":2.0,"text":"two"}],
+                "words":[{"word":"One","start":0.1,"end":0.6},{"word":"two","start":1.1,"end":1.5}]}"#,
+        );
+        let mut settings = settings(format!("{base}/v1/audio/transcriptions"));
+        settings.transcription.translate = true;
+
+        let output = HttpBackend
+            .transcribe(&settings, &vec![0.0; 32_000], "", None)
+            .expect("transcribe");
+        assert_eq!(output.text, "One two");
+        assert!(output.translated);
+        let words: Vec<_> = output
+            .words
+            .iter()
+            .map(|word| (word.text.as_str(), word.confidence))
+            .collect();
+        assert_eq!(words, vec![("One", 1.0), ("two", 1.0)]);
+
+        let (request_line, body) = requests.recv().expect("request");
+        assert!(request_line.starts_with("POST /v1/audio/translations "));
+        assert!(body.contains("name=\"model\"\r\n\r\nwhisper-1"));
+        assert!(!body.contains("name=\"language\""));
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        assert_eq!(
+            error_message(r#"{"error":{"message":"model not found"}}"#).as_deref(),
+            Some("model not found")
+        );
+        assert_eq!(
+            error_message(r#"{"error":"failed to read WAV file"}"#).as_deref(),
+            Some("failed to read WAV file")
+        );
+        assert_eq!(error_message(r#"{"text":"ok"}"#), None);
+    }
+}
@@ -0,0 +1,1324 @@
+use NumberWord::*;
+
+/// How a number word combines with its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberWord {
+    /// 0-9.
+    Digit(u64),
+    /// A value below 100 that takes no following digit: 10-19, and Spanish 21-29 ("veinticinco").
+    Teen(u64),
+    /// 20, 30, ... 90.
+    Tens(u64),
+    /// English "hundred", multiplying the value before it.
+    Hundred,
+    /// A complete hundreds value, such as Spanish "doscientos".
+    Hundreds(u64),
+    /// thousand, million, billion.
+    Scale(u64),
+}
+
+struct Currency {
+    words: &'static [&'static str],
+    /// Words for the minor unit in "five dollars and twenty cents".
+    minor: &'static [&'static str],
+    symbol: &'static str,
+    prefix: bool,
+}
+
+/// One language's vocabulary and formatting conventions.
+struct Rules {
+    numbers: &'static [(&'static str, NumberWord)],
+    ordinals: &'static [(&'static str, NumberWord)],
+    /// Word allowed inside a number: "one hundred and five", "treinta y cinco".
+    connector: &'static str,
+    /// Whether the connector follows tens (Spanish) or hundreds and scales (English).
+    tens_connector: bool,
+    /// Whether a scale word alone means one of it ("mil").
+    bare_scales: bool,
+    /// Whether years are read as two pairs ("nineteen ninety nine").
+    paired_years: bool,
+    /// Spoken "0" in phone numbers and times ("oh").
+    zero_alias: Option<&'static str>,
+    decimal_word: &'static str,
+    decimal_separator: &'static str,
+    thousands_separator: &'static str,
+    ordinal_suffix: fn(u64) -> &'static str,
+    /// Suffixes of ordinals already written in digits ("21st").
+    written_ordinals: &'static [&'static str],
+    currencies: &'static [Currency],
+    minor_connector: &'static str,
+    percent: &'static str,
+    percent_suffix: &'static str,
+    /// Unit phrases and the suffix they become, longest phrases first.
+    units: &'static [(&'static str, &'static str)],
+    months: [&'static str; 12],
+    /// Formats digit runs the language writes as phone numbers; other lengths stay as words.
+    phone: fn(&str) -> Option<String>,
+    date: fn(&Rules, &[Token], usize) -> Option<Match>,
+    time: fn(&Rules, &[Token], usize) -> Option<Match>,
+}
+
+impl Rules {
+    fn number_word(&self, key: &str) -> Option<NumberWord> {
+        lookup(self.numbers, key)
+    }
+
+    fn ordinal_word(&self, key: &str) -> Option<NumberWord> {
+        lookup(self.ordinals, key)
+    }
+
+    fn month(&self, key: &str) -> Option<usize> {
+        self.months.iter().position(|month| *month == key)
+    }
+}
+
+fn lookup(table: &[(&str, NumberWord)], key: &str) -> Option<NumberWord> {
+    table
+        .iter()
+        .find(|(word, _)| *word == key)
+        .map(|(_, value)| *value)
+}
+
+static ENGLISH: Rules = Rules {
+    numbers: &[
+        ("zero", Digit(0)),
+        ("one", Digit(1)),
+        ("two", Digit(2)),
+        ("three", Digit(3)),
+        ("four", Digit(4)),
+        ("five", Digit(5)),
+        ("six", Digit(6)),
+        ("seven", Digit(7)),
+        ("eight", Digit(8)),
+        ("nine", Digit(9)),
+        ("ten", Teen(10)),
+        ("eleven", Teen(11)),
+        ("twelve", Teen(12)),
+        ("thirteen", Teen(13)),
+        ("fourteen", Teen(14)),
+        ("fifteen", Teen(15)),
+        ("sixteen", Teen(16)),
+        ("seventeen", Teen(17)),
+        ("eighteen", Teen(18)),
+        ("nineteen", Teen(19)),
+        ("twenty", Tens(20)),
+        ("thirty", Tens(30)),
+        ("forty", Tens(40)),
+        ("fifty", Tens(50)),
+        ("sixty", Tens(60)),
+        ("seventy", Tens(70)),
+        ("eighty", Tens(80)),
+        ("ninety", Tens(90)),
+        ("hundred", Hundred),
+        ("thousand", Scale(1_000)),
+        ("million", Scale(1_000_000)),
+        ("billion", Scale(1_000_000_000)),
+    ],
+    ordinals: &[
+        ("first", Digit(1)),
+        ("second", Digit(2)),
+        ("third", Digit(3)),
+        ("fourth", Digit(4)),
+        ("fifth", Digit(5)),
+        ("sixth", Digit(6)),
+        ("seventh", Digit(7)),
+        ("eighth", Digit(8)),
+        ("ninth", Digit(9)),
+        ("tenth", Teen(10)),
+        ("eleventh", Teen(11)),
+        ("twelfth", Teen(12)),
+        ("thirteenth", Teen(13)),
+        ("fourteenth", Teen(14)),
+        ("fifteenth", Teen(15)),
+        ("sixteenth", Teen(16)),
+        ("seventeenth", Teen(17)),
+        ("eighteenth", Teen(18)),
+        ("nineteenth", Teen(19)),
+        ("twentieth", Tens(20)),
+        ("thirtieth", Tens(30)),
+        ("fortieth", Tens(40)),
+        ("fiftieth", Tens(50)),
+        ("sixtieth", Tens(60)),
+        ("seventieth", Tens(70)),
+        ("eightieth", Tens(80)),
+        ("ninetieth", Tens(90)),
+        ("hundredth", Hundred),
+        ("thousandth", Scale(1_000)),
+        ("millionth", Scale(1_000_000)),
+    ],
+    connector: "and",
+    tens_connector: false,
+    bare_scales: false,
+    paired_years: true,
+    zero_alias: Some("oh"),
+    decimal_word: "point",
+    decimal_separator: ".",
+    thousands_separator: ",",
+    ordinal_suffix: english_ordinal_suffix,
+    written_ordinals: &["st", "nd", "rd", "th"],
+    currencies: &[
+        Currency {
+            words: &["dollars", "dollar", "bucks"],
+            minor: &["cents", "cent"],
+            symbol: "$",
+            prefix: true,
+        },
+        Currency {
+            words: &["euros", "euro"],
+            minor: &["cents", "cent"],
+            symbol: "€",
+            prefix: true,
+        },
+        Currency {
+            words: &["yen"],
+            minor: &[],
+            symbol: "¥",
+            prefix: true,
+        },
+        Currency {
+            words: &["rupees", "rupee"],
+            minor: &["paise"],
+            symbol: "₹",
+            prefix: true,
+        },
+    ],
+    minor_connector: "and",
+    percent: "percent",
+    percent_suffix: "%",
+    units: &[
+        ("kilometers per hour", " km/h"),
+        ("kilometres per hour", " km/h"),
+        ("miles per hour", " mph"),
+        ("degrees celsius", "°C"),
+        ("degrees fahrenheit", "°F"),
+        ("kilometers", " km"),
+        ("kilometer", " km"),
+        ("kilometres", " km"),
+        ("kilometre", " km"),
+        ("meters", " m"),
+        ("meter", " m"),
+        ("metres", " m"),
+        ("metre", " m"),
+        ("centimeters", " cm"),
+        ("centimeter", " cm"),
+        ("centimetres", " cm"),
+        ("millimeters", " mm"),
+        ("millimetres", " mm"),
+        ("kilograms", " kg"),
+        ("kilogram", " kg"),
+        ("kilos", " kg"),
+        ("grams", " g"),
+        ("gram", " g"),
+        ("milligrams", " mg"),
+        ("liters", " L"),
+        ("liter", " L"),
+        ("litres", " L"),
+        ("litre", " L"),
+        ("milliliters", " mL"),
+        ("millilitres", " mL"),
+        ("miles", " mi"),
+        ("mile", " mi"),
+        ("feet", " ft"),
+        ("foot", " ft"),
+        ("inches", " in"),
+        ("inch", " in"),
+        ("ounces", " oz"),
+        ("ounce", " oz"),
+        ("degrees", "°"),
+        ("degree", "°"),
+        ("kilobytes", " KB"),
+        ("megabytes", " MB"),
+        ("gigabytes", " GB"),
+        ("terabytes", " TB"),
+        ("cents", "¢"),
+        ("cent", "¢"),
+    ],
+    months: [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ],
+    phone: english_phone,
+    date: english_date,
+    time: english_time,
+};
+
+static SPANISH: Rules = Rules {
+    numbers: &[
+        ("cero", Digit(0)),
+        ("uno", Digit(1)),
+        ("un", Digit(1)),
+        ("una", Digit(1)),
+        ("dos", Digit(2)),
+        ("tres", Digit(3)),
+        ("cuatro", Digit(4)),
+        ("cinco", Digit(5)),
+        ("seis", Digit(6)),
+        ("siete", Digit(7)),
+        ("ocho", Digit(8)),
+        ("nueve", Digit(9)),
+        ("diez", Teen(10)),
+        ("once", Teen(11)),
+        ("doce", Teen(12)),
+        ("trece", Teen(13)),
+        ("catorce", Teen(14)),
+        ("quince", Teen(15)),
+        ("dieciséis", Teen(16)),
+        ("diecisiete", Teen(17)),
+        ("dieciocho", Teen(18)),
+        ("diecinueve", Teen(19)),
+        ("veinte", Tens(20)),
+        ("veintiuno", Teen(21)),
+        ("veintiún", Teen(21)),
+        ("veintiuna", Teen(21)),
+        ("veintidós", Teen(22)),
+        ("veintitrés", Teen(23)),
+        ("veinticuatro", Teen(24)),
+        ("veinticinco", Teen(25)),
+        ("veintiséis", Teen(26)),
+        ("veintisiete", Teen(27)),
+        ("veintiocho", Teen(28)),
+        ("veintinueve", Teen(29)),
+        ("treinta", Tens(30)),
+        ("cuarenta", Tens(40)),
+        ("cincuenta", Tens(50)),
+        ("sesenta", Tens(60)),
+        ("setenta", Tens(70)),
+        ("ochenta", Tens(80)),
+        ("noventa", Tens(90)),
+        ("cien", Hundreds(100)),
+        ("ciento", Hundreds(100)),
+        ("doscientos", Hundreds(200)),
+        ("doscientas", Hundreds(200)),
+        ("trescientos", Hundreds(300)),
+        ("trescientas", Hundreds(300)),
+        ("cuatrocientos", Hundreds(400)),
+        ("cuatrocientas", Hundreds(400)),
+        ("quinientos", Hundreds(500)),
+        ("quinientas", Hundreds(500)),
+        ("seiscientos", Hundreds(600)),
+        ("seiscientas", Hundreds(600)),
+        ("setecientos", Hundreds(700)),
+        ("setecientas", Hundreds(700)),
+        ("ochocientos", Hundreds(800)),
+        ("ochocientas", Hundreds(800)),
+        ("novecientos", Hundreds(900)),
+        ("novecientas", Hundreds(900)),
+        ("mil", Scale(1_000)),
+        ("millón", Scale(1_000_000)),
+        ("millones", Scale(1_000_000)),
+    ],
+    ordinals: &[("primero", Digit(1)), ("primer", Digit(1))],
+    connector: "y",
+    tens_connector: true,
+    bare_scales: true,
+    paired_years: false,
+    zero_alias: None,
+    decimal_word: "coma",
+    decimal_separator: ",",
+    thousands_separator: " ",
+    ordinal_suffix: spanish_ordinal_suffix,
+    written_ordinals: &[],
+    currencies: &[
+        Currency {
+            words: &["euros", "euro"],
+            minor: &["céntimos", "céntimo"],
+            symbol: "€",
+            prefix: false,
+        },
+        Currency {
+            words: &["dólares", "dólar"],
+            minor: &["centavos", "centavo"],
+            symbol: "$",
+            prefix: false,
+        },
+    ],
+    minor_connector: "con",
+    percent: "por ciento",
+    percent_suffix: " %",
+    units: &[
+        ("kilómetros por hora", " km/h"),
+        ("grados centígrados", " °C"),
+        ("grados celsius", " °C"),
+        ("kilómetros", " km"),
+        ("kilómetro", " km"),
+        ("metros", " m"),
+        ("metro", " m"),
+        ("centímetros", " cm"),
+        ("centímetro", " cm"),
+        ("milímetros", " mm"),
+        ("kilogramos", " kg"),
+        ("kilogramo", " kg"),
+        ("kilos", " kg"),
+        ("kilo", " kg"),
+        ("gramos", " g"),
+        ("gramo", " g"),
+        ("litros", " l"),
+        ("litro", " l"),
+        ("mililitros", " ml"),
+        ("grados", " °"),
+        ("megas", " MB"),
+        ("gigas", " GB"),
+    ],
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    phone: spanish_phone,
+    date: spanish_date,
+    time: spanish_time,
+};
+
+fn rules(language: &str) -> Option<&'static Rules> {
+    match language.trim().to_ascii_lowercase().as_str() {
+        "en" => Some(&ENGLISH),
+        "es" => Some(&SPANISH),
+        _ => None,
+    }
+}
+
+/// Rewrite spoken-form numbers, currency, percentages, units, times, dates and phone numbers in
+/// `text` into written form ("twenty five dollars" -> "$25"). Whitespace and punctuation around
+/// rewritten spans are kept. Single-word numbers below ten stay as words unless they carry a unit.
+pub fn normalize(text: &str, language: &str) -> String {
+    let Some(rules) = rules(language) else {
+        return text.to_string();
+    };
+    let (prefix, tokens) = tokenize(text, rules);
+    let mut out = String::with_capacity(text.len());
+    out.push_str(prefix);
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let (text, len, rewritten) = match rewrite(rules, &tokens, index) {
+            Some(found) => (found.text, found.len, true),
+            None => (tokens[index].core.to_string(), 1, false),
+        };
+        let last = &tokens[index + len - 1];
+        let mut trail = last.trail;
+        // The final period of "p.m." only stays when it also ends the sentence.
+        let sentence_continues = tokens
+            .get(index + len)
+            .and_then(|next| next.core.chars().next())
+            .is_some_and(char::is_lowercase);
+        if rewritten && last.core.contains('.') && sentence_continues {
+            trail = trail.strip_prefix('.').unwrap_or(trail);
+        }
+        out.push_str(tokens[index].lead);
+        out.push_str(&text);
+        out.push_str(trail);
+        out.push_str(last.gap);
+        index += len;
+    }
+    out
+}
+
+struct Token<'a> {
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+    /// Lowercase core without inner punctuation ("p.m" -> "pm").
+    key: String,
+    /// Whitespace (or the hyphen of a split compound) after the token.
+    gap: &'a str,
+}
+
+struct Match {
+    /// Tokens consumed.
+    len: usize,
+    text: String,
+}
+
+struct Number {
+    value: u64,
+    decimals: Option<String>,
+    len: usize,
+    ordinal: bool,
+    /// Spoken as words rather than already written in digits.
+    spelled: bool,
+}
+
+fn tokenize<'a>(text: &'a str, rules: &Rules) -> (&'a str, Vec<Token<'a>>) {
+    let body = text.trim_start();
+    let prefix = &text[..text.len() - body.len()];
+    let mut tokens = Vec::new();
+    let mut rest = body;
+    while !rest.is_empty() {
+        let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let after = &rest[word_end..];
+        let gap_len = after.len() - after.trim_start().len();
+        push_word(&mut tokens, &rest[..word_end], &after[..gap_len], rules);
+        rest = &after[gap_len..];
+    }
+    (prefix, tokens)
+}
+
+fn push_word<'a>(tokens: &mut Vec<Token<'a>>, word: &'a str, gap: &'a str, rules: &Rules) {
+    let start = word.find(char::is_alphanumeric).unwrap_or(word.len());
+    let end = word
+        .char_indices()
+        .rev()
+        .find(|(_, ch)| ch.is_alphanumeric())
+        .map_or(start, |(index, ch)| index + ch.len_utf8());
+    let (lead, core, trail) = (&word[..start], &word[start..end], &word[end..]);
+
+    // "twenty-five" is two number words; "well-known" stays one token.
+    let parts: Vec<&str> = core.split('-').collect();
+    let numeric = |part: &&str| {
+        let key = key(part);
+        rules.number_word(&key).is_some() || rules.ordinal_word(&key).is_some()
+    };
+    if parts.len() > 1 && parts.iter().all(numeric) {
+        let last = parts.len() - 1;
+        for (index, part) in parts.into_iter().enumerate() {
+            tokens.push(Token {
+                lead: if index == 0 { lead } else { "" },
+                core: part,
+                trail: if index == last { trail } else { "" },
+                key: key(part),
+                gap: if index == last { gap } else { "-" },
+            });
+        }
+        return;
+    }
+    tokens.push(Token {
+        lead,
+        core,
+        trail,
+        key: key(core),
+        gap,
+    });
+}
+
+fn key(core: &str) -> String {
+    core.chars()
+        .filter(|ch| ch.is_alphanumeric() || *ch == '\'')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether `tokens[index]` continues a span from the token before it: no punctuation or line
+/// break in between.
+fn linked(tokens: &[Token], index: usize) -> bool {
+    index > 0
+        && index < tokens.len()
+        && tokens[index - 1].trail.is_empty()
+        && tokens[index].lead.is_empty()
+        && !tokens[index - 1].gap.contains('\n')
+}
+
+/// Number of tokens matching the space-separated `phrase` at `index`, continuing a span.
+fn phrase_len(tokens: &[Token], index: usize, phrase: &str) -> Option<usize> {
+    let mut len = 0;
+    for word in phrase.split(' ') {
+        let position = index + len;
+        if !linked(tokens, position) || tokens[position].key != word {
+            return None;
+        }
+        len += 1;
+    }
+    Some(len)
+}
+
+fn rewrite(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    phone(rules, tokens, index)
+        .or_else(|| (rules.date)(rules, tokens, index))
+        .or_else(|| (rules.time)(rules, tokens, index))
+        .or_else(|| quantity(rules, tokens, index))
+}
+
+fn number(rules: &Rules, tokens: &[Token], index: usize) -> Option<Number> {
+    let token = tokens.get(index)?;
+    if let Some(number) = written_number(rules, token.core) {
+        return Some(number);
+    }
+    if rules.paired_years {
+        if let Some(year) = paired_year(rules, tokens, index) {
+            return Some(year);
+        }
+    }
+    let (value, len, ordinal) = spelled_number(rules, tokens, index)?;
+    let decimals = if ordinal {
+        None
+    } else {
+        decimal_digits(rules, tokens, index + len)
+    };
+    Some(Number {
+        value,
+        len: len + decimals.as_ref().map_or(0, |digits| digits.len() + 1),
+        decimals,
+        ordinal,
+        spelled: true,
+    })
+}
+
+/// "25", "3.5" (with the language's decimal separator) and "21st".
+fn written_number(rules: &Rules, core: &str) -> Option<Number> {
+    let lower = core.to_ascii_lowercase();
+    let (body, ordinal) = match rules
+        .written_ordinals
+        .iter()
+        .find_map(|suffix| lower.strip_suffix(suffix))
+    {
+        Some(body) => (body, true),
+        None => (lower.as_str(), false),
+    };
+    let (integer, decimals) = match body.split_once(rules.decimal_separator) {
+        Some((integer, decimals)) if !ordinal => (integer, Some(decimals)),
+        _ => (body, None),
+    };
+    let all_digits =
+        |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    if !all_digits(integer) || decimals.is_some_and(|part| !all_digits(part)) {
+        return None;
+    }
+    Some(Number {
+        value: integer.parse().ok()?,
+        decimals: decimals.map(str::to_string),
+        len: 1,
+        ordinal,
+        spelled: false,
+    })
+}
+
+/// Parse number words from `index`; returns the value, tokens used and whether the last word was
+/// an ordinal.
+fn spelled_number(rules: &Rules, tokens: &[Token], index: usize) -> Option<(u64, usize, bool)> {
+    let mut total = 0;
+    let mut group = 0;
+    let mut last: Option<NumberWord> = None;
+    let mut smallest_scale = u64::MAX;
+    let mut end = index;
+    let mut ordinal = false;
+
+    let mut position = index;
+    while position < tokens.len() {
+        if position > index && !linked(tokens, position) {
+            break;
+        }
+        let key = tokens[position].key.as_str();
+        let connects = if rules.tens_connector {
+            matches!(last, Some(Tens(_)))
+        } else {
+            matches!(last, Some(Hundred | Scale(_)))
+        };
+        if key == rules.connector && connects {
+            let next = position + 1;
+            let followed = linked(tokens, next)
+                && (rules.number_word(&tokens[next].key).is_some()
+                    || rules.ordinal_word(&tokens[next].key).is_some());
+            if !followed {
+                break;
+            }
+            position = next;
+            continue;
+        }
+
+        let (word, is_ordinal) = match rules.number_word(key) {
+            Some(word) => (word, false),
+            None => match rules.ordinal_word(key) {
+                Some(word) => (word, true),
+                None => break,
+            },
+        };
+        let after_group = matches!(last, None | Some(Hundred | Hundreds(_) | Scale(_)));
+        let accepted = match word {
+            Digit(_) => after_group || matches!(last, Some(Tens(_))),
+            Teen(_) | Tens(_) => after_group,
+            Hundred => {
+                matches!(last, Some(Digit(_) | Teen(_) | Tens(_))) && (1..100).contains(&group)
+            }
+            Hundreds(_) => matches!(last, None | Some(Scale(_))),
+            Scale(scale) => {
+                scale < smallest_scale
+                    && (group > 0 || (rules.bare_scales && matches!(last, None | Some(Scale(_)))))
+            }
+        };
+        if !accepted {
+            break;
+        }
+        match word {
+            Digit(value) | Teen(value) | Tens(value) | Hundreds(value) => group += value,
+            Hundred => group *= 100,
+            Scale(scale) => {
+                total += group.max(1) * scale;
+                group = 0;
+                smallest_scale = scale;
+            }
+        }
+        last = Some(word);
+        position += 1;
+        end = position;
+        if is_ordinal {
+            ordinal = true;
+            break;
+        }
+    }
+
+    (end > index).then_some((total + group, end - index, ordinal))
+}
+
+/// Digits after the decimal word: "three point one four".
+fn decimal_digits(rules: &Rules, tokens: &[Token], index: usize) -> Option<String> {
+    if !linked(tokens, index) || tokens[index].key != rules.decimal_word {
+        return None;
+    }
+    let mut digits = String::new();
+    let mut position = index + 1;
+    while let Some(digit) = digit_at(rules, tokens, position) {
+        digits.push(digit);
+        position += 1;
+    }
+    (!digits.is_empty()).then_some(digits)
+}
+
+fn digit_at(rules: &Rules, tokens: &[Token], index: usize) -> Option<char> {
+    if !linked(tokens, index) {
+        return None;
+    }
+    let key = tokens[index].key.as_str();
+    match rules.number_word(key) {
+        Some(Digit(digit)) => char::from_digit(digit as u32, 10),
+        _ if rules.zero_alias == Some(key) => Some('0'),
+        _ => None,
+    }
+}
+
+/// "nineteen ninety nine", "twenty twenty four", "eighteen oh five". Only centuries 17-20, so
+/// "twelve fifteen" is left for the time rule.
+fn paired_year(rules: &Rules, tokens: &[Token], index: usize) -> Option<Number> {
+    let (century, century_len) = two_digits(rules, tokens, index)?;
+    if !(17..=20).contains(&century) {
+        return None;
+    }
+    let next = index + century_len;
+    if !linked(tokens, next) {
+        return None;
+    }
+    let (rest, rest_len) = if rules.zero_alias == Some(tokens[next].key.as_str()) {
+        let digit = digit_at(rules, tokens, next + 1)?;
+        (u64::from(digit.to_digit(10)?), 2)
+    } else {
+        let (rest, len) = two_digits(rules, tokens, next)?;
+        if rest < 10 {
+            return None;
+        }
+        (rest, len)
+    };
+    Some(Number {
+        value: century * 100 + rest,
+        decimals: None,
+        len: century_len + rest_len,
+        ordinal: false,
+        spelled: true,
+    })
+}
+
+/// A value from 10 to 99 in one or two words.
+fn two_digits(rules: &Rules, tokens: &[Token], index: usize) -> Option<(u64, usize)> {
+    match rules.number_word(&tokens.get(index)?.key)? {
+        Teen(value) => Some((value, 1)),
+        Tens(value) => match digit_at(rules, tokens, index + 1) {
+            Some(digit) if digit != '0' && rules.zero_alias != Some(&tokens[index + 1].key) => {
+                Some((value + u64::from(digit.to_digit(10)?), 2))
+            }
+            _ => Some((value, 1)),
+        },
+        _ => None,
+    }
+}
+
+fn quantity(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    let number = number(rules, tokens, index)?;
+    let next = index + number.len;
+
+    if number.ordinal {
+        if !number.spelled
+            || (number.len == 1 && number.value < 10)
+            || beside_number(rules, tokens, index, number.len)
+        {
+            return None;
+        }
+        return Some(Match {
+            len: number.len,
+            text: format!(
+                "{}{}",
+                format_integer(rules, number.value),
+                (rules.ordinal_suffix)(number.value)
+            ),
+        });
+    }
+
+    if let Some(found) = currency(rules, tokens, &number, next) {
+        return Some(found);
+    }
+    if let Some(len) = phrase_len(tokens, next, rules.percent) {
+        return Some(Match {
+            len: number.len + len,
+            text: format!("{}{}", format_number(rules, &number), rules.percent_suffix),
+        });
+    }
+    for (phrase, suffix) in rules.units {
+        if let Some(len) = phrase_len(tokens, next, phrase) {
+            return Some(Match {
+                len: number.len + len,
+                text: format!("{}{}", format_number(rules, &number), suffix),
+            });
+        }
+    }
+
+    let small = number.len == 1 && number.value < 10 && number.decimals.is_none();
+    if !number.spelled || small || beside_number(rules, tokens, index, number.len) {
+        return None;
+    }
+    Some(Match {
+        len: number.len,
+        text: format_number(rules, &number),
+    })
+}
+
+/// Whether a number word touches the span, as in "nine fifteen", where converting one half would
+/// misread the run.
+fn beside_number(rules: &Rules, tokens: &[Token], index: usize, len: usize) -> bool {
+    let is_number = |position: usize| {
+        rules.number_word(&tokens[position].key).is_some()
+            || rules.ordinal_word(&tokens[position].key).is_some()
+    };
+    (linked(tokens, index) && is_number(index - 1))
+        || (linked(tokens, index + len) && is_number(index + len))
+}
+
+fn currency(rules: &Rules, tokens: &[Token], number: &Number, next: usize) -> Option<Match> {
+    let (currency, word_len) = rules.currencies.iter().find_map(|currency| {
+        currency
+            .words
+            .iter()
+            .find_map(|word| phrase_len(tokens, next, word))
+            .map(|len| (currency, len))
+    })?;
+    let mut len = number.len + word_len;
+
+    let amount = match minor_amount(rules, tokens, currency, next + word_len) {
+        Some((minor, minor_len)) if number.decimals.is_none() => {
+            len += minor_len;
+            format!(
+                "{}{}{:02}",
+                format_integer(rules, number.value),
+                rules.decimal_separator,
+                minor
+            )
+        }
+        _ => match &number.decimals {
+            Some(decimals) if decimals.len() == 1 => format!(
+                "{}{}{}0",
+                format_integer(rules, number.value),
+                rules.decimal_separator,
+                decimals
+            ),
+            _ => format_number(rules, number),
+        },
+    };
+    let text = if currency.prefix {
+        format!("{}{}", currency.symbol, amount)
+    } else {
+        format!("{} {}", amount, currency.symbol)
+    };
+    Some(Match { len, text })
+}
+
+/// "and twenty cents" after a currency word.
+fn minor_amount(
+    rules: &Rules,
+    tokens: &[Token],
+    currency: &Currency,
+    index: usize,
+) -> Option<(u64, usize)> {
+    let start = match phrase_len(tokens, index, rules.minor_connector) {
+        Some(len) => index + len,
+        None => index,
+    };
+    if !linked(tokens, start) {
+        return None;
+    }
+    let minor = number(rules, tokens, start)?;
+    if minor.ordinal || minor.decimals.is_some() || minor.value >= 100 {
+        return None;
+    }
+    let word_len = currency
+        .minor
+        .iter()
+        .find_map(|word| phrase_len(tokens, start + minor.len, word))?;
+    Some((minor.value, start - index + minor.len + word_len))
+}
+
+/// Whether the digits at `index` continue the ones before it; commas and dashes may separate
+/// them but sentences may not.
+fn phone_separated(tokens: &[Token], index: usize) -> bool {
+    let previous = &tokens[index - 1];
+    matches!(previous.trail, "" | "," | "-")
+        && tokens[index].lead.is_empty()
+        && !previous.gap.contains('\n')
+}
+
+fn phone_digit(rules: &Rules, token: &Token) -> Option<char> {
+    let key = token.key.as_str();
+    match rules.number_word(key) {
+        Some(Digit(digit)) => char::from_digit(digit as u32, 10),
+        _ if rules.zero_alias == Some(key) => Some('0'),
+        _ => None,
+    }
+}
+
+fn phone(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    // Only a whole run can be a phone number, not its tail.
+    if index > 0
+        && phone_separated(tokens, index)
+        && phone_digit(rules, &tokens[index - 1]).is_some()
+    {
+        return None;
+    }
+    let mut digits = String::new();
+    let mut position = index;
+    while position < tokens.len() {
+        if position > index && !phone_separated(tokens, position) {
+            break;
+        }
+        let Some(digit) = phone_digit(rules, &tokens[position]) else {
+            break;
+        };
+        digits.push(digit);
+        position += 1;
+    }
+    let text = (rules.phone)(&digits)?;
+    Some(Match {
+        len: position - index,
+        text,
+    })
+}
+
+fn format_integer(rules: &Rules, value: u64) -> String {
+    let digits = value.to_string();
+    // Four-digit numbers are often years; leave them ungrouped.
+    if value < 10_000 {
+        return digits;
+    }
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, ch) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            out.push_str(rules.thousands_separator);
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn format_number(rules: &Rules, number: &Number) -> String {
+    let integer = format_integer(rules, number.value);
+    match &number.decimals {
+        Some(decimals) => format!("{integer}{}{decimals}", rules.decimal_separator),
+        None => integer,
+    }
+}
+
+fn english_ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+fn spanish_ordinal_suffix(_value: u64) -> &'static str {
+    ".º"
+}
+
+fn english_phone(digits: &str) -> Option<String> {
+    match digits.len() {
+        7 => Some(format!("{}-{}", &digits[..3], &digits[3..])),
+        10 => Some(format!(
+            "{}-{}-{}",
+            &digits[..3],
+            &digits[3..6],
+            &digits[6..]
+        )),
+        11 if digits.starts_with('1') => Some(format!(
+            "1-{}-{}-{}",
+            &digits[1..4],
+            &digits[4..7],
+            &digits[7..]
+        )),
+        _ => None,
+    }
+}
+
+fn spanish_phone(digits: &str) -> Option<String> {
+    match digits.len() {
+        9 => Some(format!(
+            "{} {} {}",
+            &digits[..3],
+            &digits[3..6],
+            &digits[6..]
+        )),
+        _ => None,
+    }
+}
+
+fn day_at(rules: &Rules, tokens: &[Token], index: usize) -> Option<Number> {
+    if !linked(tokens, index) {
+        return None;
+    }
+    let day = number(rules, tokens, index)?;
+    (day.decimals.is_none() && (1..=31).contains(&day.value)).then_some(day)
+}
+
+fn year_at(rules: &Rules, tokens: &[Token], index: usize) -> Option<Number> {
+    let year = number(rules, tokens, index)?;
+    (!year.ordinal && year.decimals.is_none() && (1_000..=2_999).contains(&year.value))
+        .then_some(year)
+}
+
+/// A year after "March 3" or "March 3," in English.
+fn english_year(rules: &Rules, tokens: &[Token], index: usize) -> Option<Number> {
+    let previous = tokens.get(index.checked_sub(1)?)?;
+    let token = tokens.get(index)?;
+    let separated =
+        matches!(previous.trail, "" | ",") && token.lead.is_empty() && !previous.gap.contains('\n');
+    separated.then(|| year_at(rules, tokens, index)).flatten()
+}
+
+fn english_month_name(rules: &Rules, month: usize) -> String {
+    let name = rules.months[month];
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// "March third", "March 3rd, twenty twenty four", "the third of March".
+fn english_date(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    let (month, day, mut end) = if let Some(month) = rules.month(&tokens[index].key) {
+        let mut day_index = index + 1;
+        if phrase_len(tokens, day_index, "the").is_some() {
+            day_index += 1;
+        }
+        let day = day_at(rules, tokens, day_index)?;
+        // "may two" and "march five" are more often verbs; a cardinal day needs "March" written
+        // as a name.
+        let capitalized = tokens[index].core.starts_with(char::is_uppercase);
+        if !day.ordinal && !capitalized {
+            return None;
+        }
+        let end = day_index + day.len;
+        (month, day, end)
+    } else {
+        let day_index = if tokens[index].key == "the" {
+            index + 1
+        } else {
+            index
+        };
+        let day = if day_index == index {
+            number(rules, tokens, index)?
+        } else {
+            day_at(rules, tokens, day_index)?
+        };
+        if !day.ordinal || !(1..=31).contains(&day.value) {
+            return None;
+        }
+        let of = day_index + day.len;
+        phrase_len(tokens, of, "of")?;
+        if !linked(tokens, of + 1) {
+            return None;
+        }
+        let month = rules.month(&tokens[of + 1].key)?;
+        (month, day, of + 2)
+    };
+
+    let mut text = format!("{} {}", english_month_name(rules, month), day.value);
+    if let Some(year) = english_year(rules, tokens, end) {
+        text.push_str(&format!(", {}", year.value));
+        end += year.len;
+    }
+    Some(Match {
+        len: end - index,
+        text,
+    })
+}
+
+/// "three thirty pm", "eight oh five a.m.", "seven o'clock", "at nine fifteen", "3:30 p.m.".
+fn english_time(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    if let Some((hour, minutes)) = tokens[index].core.split_once(':') {
+        let clock = (1..=2).contains(&hour.len())
+            && minutes.len() == 2
+            && hour
+                .bytes()
+                .chain(minutes.bytes())
+                .all(|byte| byte.is_ascii_digit());
+        if !clock {
+            return None;
+        }
+        let (suffix, len) = meridiem(tokens, index + 1)?;
+        return Some(Match {
+            len: 1 + len,
+            text: format!("{hour}:{minutes} {suffix}"),
+        });
+    }
+
+    let hour = number(rules, tokens, index)?;
+    if hour.ordinal || hour.decimals.is_some() || hour.len != 1 || !(1..=12).contains(&hour.value) {
+        return None;
+    }
+    let mut end = index + 1;
+    let mut minutes = None;
+    let mut oclock = false;
+    if linked(tokens, end) {
+        let key = tokens[end].key.as_str();
+        if key == "o'clock" || key == "oclock" {
+            oclock = true;
+            end += 1;
+        } else if rules.zero_alias == Some(key) {
+            if let Some(digit) = digit_at(rules, tokens, end + 1) {
+                minutes = digit.to_digit(10).map(u64::from);
+                end += 2;
+            }
+        } else if let Some(number) = number(rules, tokens, end) {
+            if !number.ordinal && number.decimals.is_none() && (10..=59).contains(&number.value) {
+                minutes = Some(number.value);
+                end += number.len;
+            }
+        }
+    }
+    let meridiem = meridiem(tokens, end);
+    // Without am/pm, "three thirty" is only a time after "at".
+    let after_at = index > 0 && tokens[index - 1].key == "at" && linked(tokens, index);
+    if meridiem.is_none() && !oclock && !(after_at && minutes.is_some()) {
+        return None;
+    }
+
+    let mut text = hour.value.to_string();
+    match minutes {
+        Some(minutes) => text.push_str(&format!(":{minutes:02}")),
+        None if oclock => text.push_str(":00"),
+        None => {}
+    }
+    if let Some((suffix, len)) = meridiem {
+        text.push(' ');
+        text.push_str(suffix);
+        end += len;
+    }
+    Some(Match {
+        len: end - index,
+        text,
+    })
+}
+
+fn meridiem(tokens: &[Token], index: usize) -> Option<(&'static str, usize)> {
+    if !linked(tokens, index) {
+        return None;
+    }
+    match tokens[index].key.as_str() {
+        "am" => Some(("AM", 1)),
+        "pm" => Some(("PM", 1)),
+        _ => None,
+    }
+}
+
+/// "tres de marzo de dos mil veinticuatro", "primero de mayo".
+fn spanish_date(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    let day = number(rules, tokens, index)?;
+    if day.decimals.is_some() || !(1..=31).contains(&day.value) {
+        return None;
+    }
+    let of = index + day.len;
+    phrase_len(tokens, of, "de")?;
+    if !linked(tokens, of + 1) {
+        return None;
+    }
+    rules.month(&tokens[of + 1].key)?;
+    let mut end = of + 2;
+    let mut text = format!("{} de {}", day.value, tokens[of + 1].core);
+    if phrase_len(tokens, end, "de").is_some() && linked(tokens, end + 1) {
+        if let Some(year) = year_at(rules, tokens, end + 1) {
+            text.push_str(&format!(" de {}", year.value));
+            end += 1 + year.len;
+        }
+    }
+    Some(Match {
+        len: end - index,
+        text,
+    })
+}
+
+/// "las tres y media", "la una y cuarto", "las diez en punto".
+fn spanish_time(rules: &Rules, tokens: &[Token], index: usize) -> Option<Match> {
+    if !matches!(tokens[index].key.as_str(), "la" | "las") || !linked(tokens, index + 1) {
+        return None;
+    }
+    let hour = number(rules, tokens, index + 1)?;
+    if hour.ordinal || hour.decimals.is_some() || hour.len != 1 || !(1..=12).contains(&hour.value) {
+        return None;
+    }
+    let after_hour = index + 2;
+    let (minutes, end) = if phrase_len(tokens, after_hour, "y").is_some() {
+        let minute_index = after_hour + 1;
+        if !linked(tokens, minute_index) {
+            return None;
+        }
+        match tokens[minute_index].key.as_str() {
+            "media" => (30, minute_index + 1),
+            "cuarto" => (15, minute_index + 1),
+            _ => {
+                let minutes = number(rules, tokens, minute_index)?;
+                if minutes.ordinal
+                    || minutes.decimals.is_some()
+                    || !(1..=59).contains(&minutes.value)
+                {
+                    return None;
+                }
+                (minutes.value, minute_index + minutes.len)
+            }
+        }
+    } else {
+        let len = phrase_len(tokens, after_hour, "en punto")?;
+        (0, after_hour + len)
+    };
+    Some(Match {
+        len: end - index,
+        text: format!("{} {}:{:02}", tokens[index].core, hour.value, minutes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en(text: &str) -> String {
+        normalize(text, "en")
+    }
+
+    fn es(text: &str) -> String {
+        normalize(text, "es")
+    }
+
+    #[test]
+    fn english_cardinals_and_ordinals() {
+        assert_eq!(en("I counted twenty five"), "I counted 25");
+        assert_eq!(en("one hundred and five people"), "105 people");
+        assert_eq!(en("two thousand twenty four"), "2024");
+        assert_eq!(en("three million four hundred thousand"), "3,400,000");
+        assert_eq!(en("twenty-five"), "25");
+        assert_eq!(en("pi is three point one four"), "pi is 3.14");
+        assert_eq!(en("in nineteen ninety nine"), "in 1999");
+        assert_eq!(en("the twenty first time"), "the 21st time");
+        assert_eq!(en("her eleventh birthday"), "her 11th birthday");
+        // Small numbers and ordinals read better as words.
+        assert_eq!(en("one of the first two"), "one of the first two");
+        assert_eq!(en("one two three"), "one two three");
+        assert_eq!(en("a well-known fact"), "a well-known fact");
+    }
+
+    #[test]
+    fn english_currency_percent_and_units() {
+        assert_eq!(en("twenty five dollars"), "$25");
+        assert_eq!(en("five dollars and twenty cents"), "$5.20");
+        assert_eq!(en("three point five euros"), "€3.50");
+        assert_eq!(en("it costs 40 dollars."), "it costs $40.");
+        assert_eq!(en("twenty five cents"), "25¢");
+        assert_eq!(en("fifty percent off"), "50% off");
+        assert_eq!(en("two point five percent"), "2.5%");
+        assert_eq!(en("five kilometers"), "5 km");
+        assert_eq!(en("sixty miles per hour"), "60 mph");
+        assert_eq!(en("seventy two degrees fahrenheit"), "72°F");
+        assert_eq!(en("sixteen gigabytes"), "16 GB");
+    }
+
+    #[test]
+    fn english_times_dates_and_phone_numbers() {
+        assert_eq!(
+            en("twenty five dollars on march third at three thirty pm"),
+            "$25 on March 3 at 3:30 PM"
+        );
+        assert_eq!(en("call me at eight oh five a.m."), "call me at 8:05 AM.");
+        assert_eq!(en("around seven o'clock"), "around 7:00");
+        assert_eq!(en("at nine fifteen"), "at 9:15");
+        assert_eq!(en("by 3:30 p.m. today"), "by 3:30 PM today");
+        assert_eq!(en("nine fifteen"), "nine fifteen");
+        assert_eq!(
+            en("on the fourth of July, nineteen seventy six"),
+            "on July 4, 1976"
+        );
+        assert_eq!(
+            en("due March 3rd, twenty twenty four."),
+            "due March 3, 2024."
+        );
+        assert_eq!(en("Due May 5 please"), "Due May 5 please");
+        assert_eq!(en("you may two"), "you may two");
+        assert_eq!(
+            en("my number is five five five one two three four"),
+            "my number is 555-1234"
+        );
+        assert_eq!(
+            en("call four one five, five five five, oh one nine nine."),
+            "call 415-555-0199."
+        );
+        assert_eq!(
+            en("one two three four five six seven eight nine"),
+            "one two three four five six seven eight nine"
+        );
+    }
+
+    #[test]
+    fn keeps_punctuation_and_line_breaks() {
+        assert_eq!(
+            en("Total: twenty dollars,\nthen thirty."),
+            "Total: $20,\nthen 30."
+        );
+        assert_eq!(en("twenty\nfive"), "20\nfive");
+        assert_eq!(en("  (twelve)  "), "  (12)  ");
+    }
+
+    #[test]
+    fn spanish_rules() {
+        assert_eq!(es("veinticinco euros"), "25 €");
+        assert_eq!(es("treinta y cinco kilómetros"), "35 km");
+        assert_eq!(es("el cincuenta por ciento"), "el 50 %");
+        assert_eq!(
+            es("el tres de marzo de dos mil veinticuatro"),
+            "el 3 de marzo de 2024"
+        );
+        assert_eq!(es("a las tres y media"), "a las 3:30");
+        assert_eq!(es("a las diez en punto"), "a las 10:00");
+        assert_eq!(es("mil doscientos"), "1200");
+        assert_eq!(es("cinco euros con veinte céntimos"), "5,20 €");
+        assert_eq!(es("tengo dos gatos"), "tengo dos gatos");
+    }
+
+    #[test]
+    fn other_languages_pass_through() {
+        assert_eq!(
+            normalize("fünfundzwanzig Euro", "de"),
+            "fünfundzwanzig Euro"
+        );
+    }
+}
@@ -2,12 +2,25 @@ pub mod audio;
 pub mod audio_import;
 pub mod automation;
 pub mod autostart;
+pub mod benchmark;
+pub mod continuation;
+pub mod dictation;
 pub mod embedding;
+pub mod evaluation;
+pub mod hallucination;
 #[allow(dead_code)]
 pub mod hotkeys;
+pub mod http_backend;
+pub mod itn;
 pub mod macos_permissions;
 pub mod models;
+pub mod phonetic;
+pub mod replacements;
+pub mod resample;
 pub mod runtime;
+pub mod speakers;
 pub mod storage;
+pub mod streaming;
 pub mod summary;
 pub mod transcription;
+pub mod vocabulary;
@@ -13,23 +13,61 @@ struct ModelDefinition {
     id: &'static str,
     label: &'static str,
     filename: &'static str,
+    // `.en` models are English-only and cannot detect or translate other languages.
+    multilingual: bool,
+    /// Tinydiarize fine-tune that emits speaker-turn tokens.
+    speaker_turns: bool,
 }
 
-const MODELS: [ModelDefinition; 3] = [
+const MODELS: [ModelDefinition; 7] = [
     ModelDefinition {
         id: "tiny.en",
         label: "Tiny (fast)",
         filename: "ggml-tiny.en.bin",
+        multilingual: false,
+        speaker_turns: false,
+    },
+    ModelDefinition {
+        id: "tiny",
+        label: "Tiny multilingual (fast)",
+        filename: "ggml-tiny.bin",
+        multilingual: true,
+        speaker_turns: false,
     },
     ModelDefinition {
         id: "small.en",
         label: "Small (balanced)",
         filename: "ggml-small.en.bin",
+        multilingual: false,
+        speaker_turns: false,
+    },
+    ModelDefinition {
+        id: "small",
+        label: "Small multilingual (balanced)",
+        filename: "ggml-small.bin",
+        multilingual: true,
+        speaker_turns: false,
     },
     ModelDefinition {
         id: "medium.en",
         label: "Medium (accurate)",
         filename: "ggml-medium.en.bin",
+        multilingual: false,
+        speaker_turns: false,
+    },
+    ModelDefinition {
+        id: "medium",
+        label: "Medium multilingual (accurate)",
+        filename: "ggml-medium.bin",
+        multilingual: true,
+        speaker_turns: false,
+    },
+    ModelDefinition {
+        id: "small.en-tdrz",
+        label: "Small with speaker turns (meetings)",
+        filename: "ggml-small.en-tdrz.bin",
+        multilingual: false,
+        speaker_turns: true,
     },
 ];
 
@@ -56,6 +94,7 @@ pub fn list_models(settings: &Settings) -> Vec<ModelInfo> {
             ModelInfo {
                 id: model.id.to_string(),
                 label: model.label.to_string(),
+                multilingual: model.multilingual,
                 installed,
                 // Only mark a model as active when it is usable.
                 active: installed && settings.transcription.model == model.id,
@@ -64,6 +103,29 @@ pub fn list_models(settings: &Settings) -> Vec<ModelInfo> {
         .collect()
 }
 
+pub fn supports_speaker_turns(model_id: &str) -> bool {
+    find_model(model_id)
+        .map(|model| model.speaker_turns)
+        .unwrap_or(false)
+}
+
+pub fn is_multilingual(model_id: &str) -> bool {
+    find_model(model_id)
+        .map(|model| model.multilingual)
+        .unwrap_or(false)
+}
+
+/// Translation runs whisper's translate task, which English-only models were never trained for.
+pub fn ensure_supports_translation(model_id: &str) -> Result<(), String> {
+    if is_multilingual(model_id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Model {model_id} is English-only and cannot translate; choose a multilingual model"
+        ))
+    }
+}
+
 pub fn resolve_model_path(settings: &Settings, model_id: &str) -> Result<PathBuf, String> {
     let model = find_model(model_id)?;
     let path = model_path(settings, model);
@@ -141,6 +203,9 @@ pub fn activate_model(settings: &mut Settings, model_id: &str) -> Result<(), Str
     if !model_path(settings, model).exists() {
         return Err("Model not installed".to_string());
     }
+    if settings.transcription.translate {
+        ensure_supports_translation(model.id)?;
+    }
 
     settings.transcription.model = model.id.to_string();
     Ok(())
@@ -148,7 +213,11 @@ pub fn activate_model(settings: &mut Settings, model_id: &str) -> Result<(), Str
 
 pub fn cycle_model(settings: &mut Settings) -> Result<String, String> {
     let models = list_models(settings);
-    let installed: Vec<ModelInfo> = models.into_iter().filter(|m| m.installed).collect();
+    let translate = settings.transcription.translate;
+    let installed: Vec<ModelInfo> = models
+        .into_iter()
+        .filter(|m| m.installed && (m.multilingual || !translate))
+        .collect();
 
     if installed.is_empty() {
         return Err("No installed models to cycle".to_string());
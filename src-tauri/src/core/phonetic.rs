@@ -0,0 +1,625 @@
+/// Double Metaphone keys (primary, alternate) for a word, after Lawrence Philips' algorithm.
+///
+/// Non-letters are ignored, so "Post-GREST" and "postgrest" share keys. Keys are not truncated
+/// to four characters as in the original; longer names stay distinguishable.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let chars: Vec<char> = word
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .flat_map(char::to_uppercase)
+        .collect();
+    let mut encoder = Encoder {
+        slavo_germanic: is_slavo_germanic(&chars),
+        chars,
+        primary: String::new(),
+        alternate: String::new(),
+    };
+    encoder.encode();
+    (encoder.primary, encoder.alternate)
+}
+
+fn is_slavo_germanic(chars: &[char]) -> bool {
+    let text: String = chars.iter().collect();
+    text.contains('W') || text.contains('K') || text.contains("CZ") || text.contains("WITZ")
+}
+
+fn is_vowel(ch: char) -> bool {
+    matches!(ch, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+struct Encoder {
+    chars: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl Encoder {
+    fn len(&self) -> isize {
+        self.chars.len() as isize
+    }
+
+    /// The letter at `index`, or NUL outside the word.
+    fn at(&self, index: isize) -> char {
+        if index < 0 {
+            return '\0';
+        }
+        self.chars.get(index as usize).copied().unwrap_or('\0')
+    }
+
+    /// Whether the `len` letters at `start` spell one of `options`.
+    fn contains(&self, start: isize, len: usize, options: &[&str]) -> bool {
+        if start < 0 || start as usize + len > self.chars.len() {
+            return false;
+        }
+        let slice = &self.chars[start as usize..start as usize + len];
+        options
+            .iter()
+            .any(|option| option.chars().eq(slice.iter().copied()))
+    }
+
+    fn add(&mut self, both: &str) {
+        self.primary.push_str(both);
+        self.alternate.push_str(both);
+    }
+
+    fn add_pair(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    /// Skip a doubled letter.
+    fn next_unless(&self, index: isize, doubled: char) -> isize {
+        if self.at(index + 1) == doubled {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode(&mut self) {
+        let mut index = if self.contains(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            1
+        } else {
+            0
+        };
+        while index < self.len() {
+            index = match self.at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    if index == 0 {
+                        self.add("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.add("P");
+                    self.next_unless(index, 'B')
+                }
+                'Ç' => {
+                    self.add("S");
+                    index + 1
+                }
+                'C' => self.c(index),
+                'D' => self.d(index),
+                'F' => {
+                    self.add("F");
+                    self.next_unless(index, 'F')
+                }
+                'G' => self.g(index),
+                'H' => self.h(index),
+                'J' => self.j(index),
+                'K' => {
+                    self.add("K");
+                    self.next_unless(index, 'K')
+                }
+                'L' => self.l(index),
+                'M' => {
+                    self.add("M");
+                    if self.at(index + 1) == 'M'
+                        || (self.contains(index - 1, 3, &["UMB"])
+                            && (index + 1 == self.len() - 1
+                                || self.contains(index + 2, 2, &["ER"])))
+                    {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.add("N");
+                    self.next_unless(index, 'N')
+                }
+                'Ñ' => {
+                    self.add("N");
+                    index + 1
+                }
+                'P' => {
+                    if self.at(index + 1) == 'H' {
+                        self.add("F");
+                        index + 2
+                    } else {
+                        self.add("P");
+                        if self.contains(index + 1, 1, &["P", "B"]) {
+                            index + 2
+                        } else {
+                            index + 1
+                        }
+                    }
+                }
+                'Q' => {
+                    self.add("K");
+                    self.next_unless(index, 'Q')
+                }
+                'R' => self.r(index),
+                'S' => self.s(index),
+                'T' => self.t(index),
+                'V' => {
+                    self.add("F");
+                    self.next_unless(index, 'V')
+                }
+                'W' => self.w(index),
+                'X' => self.x(index),
+                'Z' => self.z(index),
+                _ => index + 1,
+            };
+        }
+    }
+
+    fn c(&mut self, index: isize) -> isize {
+        if self.germanic_ach(index) {
+            self.add("K");
+            index + 2
+        } else if index == 0 && self.contains(index, 6, &["CAESAR"]) {
+            self.add("S");
+            index + 2
+        } else if self.contains(index, 2, &["CH"]) {
+            self.ch(index)
+        } else if self.contains(index, 2, &["CZ"]) && !self.contains(index - 2, 4, &["WICZ"]) {
+            self.add_pair("S", "X");
+            index + 2
+        } else if self.contains(index + 1, 3, &["CIA"]) {
+            self.add("X");
+            index + 3
+        } else if self.contains(index, 2, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            self.cc(index)
+        } else if self.contains(index, 2, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            index + 2
+        } else if self.contains(index, 2, &["CI", "CE", "CY"]) {
+            if self.contains(index, 3, &["CIO", "CIE", "CIA"]) {
+                self.add_pair("S", "X");
+            } else {
+                self.add("S");
+            }
+            index + 2
+        } else {
+            self.add("K");
+            if self.contains(index + 1, 1, &["C", "K", "Q"])
+                && !self.contains(index + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    /// "-ACH-" as in "Bacher", but not "Macher" or before I/E.
+    fn germanic_ach(&self, index: isize) -> bool {
+        if self.contains(index, 4, &["CHIA"]) {
+            return true;
+        }
+        if index <= 1 || is_vowel(self.at(index - 2)) || !self.contains(index - 1, 3, &["ACH"]) {
+            return false;
+        }
+        let next = self.at(index + 2);
+        (next != 'I' && next != 'E') || self.contains(index - 2, 6, &["BACHER", "MACHER"])
+    }
+
+    fn cc(&mut self, index: isize) -> isize {
+        if self.contains(index + 2, 1, &["I", "E", "H"]) && !self.contains(index + 2, 2, &["HU"]) {
+            if (index == 1 && self.at(index - 1) == 'A')
+                || self.contains(index - 1, 5, &["UCCEE", "UCCES"])
+            {
+                self.add("KS");
+            } else {
+                self.add("X");
+            }
+            index + 3
+        } else {
+            self.add("K");
+            index + 2
+        }
+    }
+
+    fn ch(&mut self, index: isize) -> isize {
+        if index > 0 && self.contains(index, 4, &["CHAE"]) {
+            self.add_pair("K", "X");
+        } else if self.greek_ch(index) || self.germanic_ch(index) {
+            self.add("K");
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                self.add("K");
+            } else {
+                self.add_pair("X", "K");
+            }
+        } else {
+            self.add("X");
+        }
+        index + 2
+    }
+
+    fn greek_ch(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn germanic_ch(&self, index: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, 1, &["T", "S"])
+            || ((self.contains(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(
+                    index + 2,
+                    1,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || index + 1 == self.len() - 1))
+    }
+
+    fn d(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["DG"]) {
+            if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+                self.add("J");
+                index + 3
+            } else {
+                self.add("TK");
+                index + 2
+            }
+        } else if self.contains(index, 2, &["DT", "DD"]) {
+            self.add("T");
+            index + 2
+        } else {
+            self.add("T");
+            index + 1
+        }
+    }
+
+    fn g(&mut self, index: isize) -> isize {
+        let next = self.at(index + 1);
+        if next == 'H' {
+            self.gh(index)
+        } else if next == 'N' {
+            if index == 1 && is_vowel(self.at(0)) && !self.slavo_germanic {
+                self.add_pair("KN", "N");
+            } else if !self.contains(index + 2, 2, &["EY"])
+                && self.at(index + 1) != 'Y'
+                && !self.slavo_germanic
+            {
+                self.add_pair("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.add_pair("KL", "L");
+            index + 2
+        } else if self.hard_or_soft_g(index) {
+            self.add_pair("K", "J");
+            index + 2
+        } else if self.contains(index + 1, 1, &["E", "I", "Y"])
+            || self.contains(index - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(index + 1, 2, &["ET"])
+            {
+                self.add("K");
+            } else if self.contains(index + 1, 3, &["IER"]) {
+                self.add("J");
+            } else {
+                self.add_pair("J", "K");
+            }
+            index + 2
+        } else {
+            self.add("K");
+            self.next_unless(index, 'G')
+        }
+    }
+
+    /// G that may be hard or soft: "Gerald", "Gibson" at the start, or "-ger-"/"-gy" inside a
+    /// word ("anger", but not "danger", "tiger" or "biology").
+    fn hard_or_soft_g(&self, index: isize) -> bool {
+        let next = self.at(index + 1);
+        let initial = index == 0
+            && (next == 'Y'
+                || self.contains(
+                    index + 1,
+                    2,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ));
+        let medial = (self.contains(index + 1, 2, &["ER"]) || next == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(index - 1, 1, &["E", "I"])
+            && !self.contains(index - 1, 3, &["RGY", "OGY"]);
+        initial || medial
+    }
+
+    fn gh(&mut self, index: isize) -> isize {
+        if index > 0 && !is_vowel(self.at(index - 1)) {
+            self.add("K");
+        } else if index == 0 {
+            if self.at(index + 2) == 'I' {
+                self.add("J");
+            } else {
+                self.add("K");
+            }
+        } else if (index > 1 && self.contains(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, 1, &["B", "H"]))
+        {
+            // Silent, as in "bough" and "bright".
+        } else if index > 2
+            && self.at(index - 1) == 'U'
+            && self.contains(index - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            self.add("F");
+        } else if self.at(index - 1) != 'I' {
+            self.add("K");
+        }
+        index + 2
+    }
+
+    fn h(&mut self, index: isize) -> isize {
+        if (index == 0 || is_vowel(self.at(index - 1))) && is_vowel(self.at(index + 1)) {
+            self.add("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn j(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            if (index == 0 && self.at(index + 4) == ' ')
+                || self.len() == 4
+                || self.contains(0, 4, &["SAN "])
+            {
+                self.add("H");
+            } else {
+                self.add_pair("J", "H");
+            }
+            return index + 1;
+        }
+        if index == 0 {
+            self.add_pair("J", "A");
+        } else if is_vowel(self.at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.at(index + 1), 'A' | 'O')
+        {
+            self.add_pair("J", "H");
+        } else if index == self.len() - 1 {
+            self.add_pair("J", "");
+        } else if !self.contains(index + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.contains(index - 1, 1, &["S", "K", "L"])
+        {
+            self.add("J");
+        }
+        self.next_unless(index, 'J')
+    }
+
+    fn l(&mut self, index: isize) -> isize {
+        if self.at(index + 1) != 'L' {
+            self.add("L");
+            return index + 1;
+        }
+        let len = self.len();
+        // Spanish "-illo", "-illa", "-alle": the L is silent in the alternate.
+        let spanish = (index == len - 3 && self.contains(index - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.contains(len - 2, 2, &["AS", "OS"])
+                || self.contains(len - 1, 1, &["A", "O"]))
+                && self.contains(index - 1, 4, &["ALLE"]));
+        if spanish {
+            self.add_pair("L", "");
+        } else {
+            self.add("L");
+        }
+        index + 2
+    }
+
+    fn r(&mut self, index: isize) -> isize {
+        // French final "-ier" is silent in the primary.
+        if index == self.len() - 1
+            && !self.slavo_germanic
+            && self.contains(index - 2, 2, &["IE"])
+            && !self.contains(index - 4, 2, &["ME", "MA"])
+        {
+            self.add_pair("", "R");
+        } else {
+            self.add("R");
+        }
+        self.next_unless(index, 'R')
+    }
+
+    fn s(&mut self, index: isize) -> isize {
+        if self.contains(index - 1, 3, &["ISL", "YSL"]) {
+            index + 1
+        } else if index == 0 && self.contains(index, 5, &["SUGAR"]) {
+            self.add_pair("X", "S");
+            index + 1
+        } else if self.contains(index, 2, &["SH"]) {
+            if self.contains(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            index + 2
+        } else if self.contains(index, 3, &["SIO", "SIA"]) || self.contains(index, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_pair("S", "X");
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(index + 1, 1, &["Z"])
+        {
+            self.add_pair("S", "X");
+            if self.contains(index + 1, 1, &["Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        } else if self.contains(index, 2, &["SC"]) {
+            self.sc(index)
+        } else {
+            if index == self.len() - 1 && self.contains(index - 2, 2, &["AI", "OI"]) {
+                self.add_pair("", "S");
+            } else {
+                self.add("S");
+            }
+            if self.contains(index + 1, 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn sc(&mut self, index: isize) -> isize {
+        if self.at(index + 2) == 'H' {
+            if self.contains(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                if self.contains(index + 3, 2, &["ER", "EN"]) {
+                    self.add_pair("X", "SK");
+                } else {
+                    self.add("SK");
+                }
+            } else if index == 0 && !is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.add_pair("X", "S");
+            } else {
+                self.add("X");
+            }
+        } else if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+            self.add("S");
+        } else {
+            self.add("SK");
+        }
+        index + 3
+    }
+
+    fn t(&mut self, index: isize) -> isize {
+        if self.contains(index, 4, &["TION"]) || self.contains(index, 3, &["TIA", "TCH"]) {
+            self.add("X");
+            index + 3
+        } else if self.contains(index, 2, &["TH"]) || self.contains(index, 3, &["TTH"]) {
+            if self.contains(index + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                self.add("T");
+            } else {
+                self.add_pair("0", "T");
+            }
+            index + 2
+        } else {
+            self.add("T");
+            if self.contains(index + 1, 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn w(&mut self, index: isize) -> isize {
+        if self.contains(index, 2, &["WR"]) {
+            self.add("R");
+            return index + 2;
+        }
+        if index == 0 && (is_vowel(self.at(index + 1)) || self.contains(index, 2, &["WH"])) {
+            if is_vowel(self.at(index + 1)) {
+                self.add_pair("A", "F");
+            } else {
+                self.add("A");
+            }
+            index + 1
+        } else if (index == self.len() - 1 && is_vowel(self.at(index - 1)))
+            || self.contains(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            self.add_pair("", "F");
+            index + 1
+        } else if self.contains(index, 4, &["WICZ", "WITZ"]) {
+            self.add_pair("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn x(&mut self, index: isize) -> isize {
+        if index == 0 {
+            self.add("S");
+            return index + 1;
+        }
+        // French final "-iaux", "-eaux", "-aux", "-oux" are silent.
+        let silent = index == self.len() - 1
+            && (self.contains(index - 3, 3, &["IAU", "EAU"])
+                || self.contains(index - 2, 2, &["AU", "OU"]));
+        if !silent {
+            self.add("KS");
+        }
+        if self.contains(index + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn z(&mut self, index: isize) -> isize {
+        if self.at(index + 1) == 'H' {
+            self.add("J");
+            return index + 2;
+        }
+        if self.contains(index + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+        {
+            self.add_pair("S", "TS");
+        } else {
+            self.add("S");
+        }
+        self.next_unless(index, 'Z')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(word: &str) -> (String, String) {
+        double_metaphone(word)
+    }
+
+    #[test]
+    fn encodes_reference_words() {
+        assert_eq!(keys("Smith"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(keys("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+        assert_eq!(keys("Thomas"), ("TMS".to_string(), "TMS".to_string()));
+        assert_eq!(keys("Knight").0, "NT");
+        assert_eq!(keys("Xavier").0, "SF");
+        assert_eq!(keys("Caesar").0, "SSR");
+        assert_eq!(keys("Philips").0, "FLPS");
+    }
+
+    #[test]
+    fn sound_alikes_share_keys() {
+        assert_eq!(keys("Kubernetes").0, keys("cooper netties").0);
+        assert_eq!(keys("PostgREST").0, keys("post grest").0);
+        assert_eq!(keys("Catherine").0, keys("Kathryn").0);
+        assert_ne!(keys("Kubernetes").0, keys("governance").0);
+    }
+}
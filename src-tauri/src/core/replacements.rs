@@ -0,0 +1,175 @@
+use regex::{Captures, Regex, RegexBuilder};
+
+use crate::types::{AppliedReplacement, ReplacementRule};
+
+/// Match the pattern text anywhere, even inside words.
+pub const MODE_LITERAL: &str = "literal";
+/// Match the pattern text only where it is not part of a longer word.
+pub const MODE_WHOLE_WORD: &str = "whole_word";
+/// Match a regular expression; the replacement may refer to groups as `$1` or `${name}`.
+pub const MODE_REGEX: &str = "regex";
+
+/// Build the matcher for a rule, or explain why it can't be used.
+pub fn compile(rule: &ReplacementRule) -> Result<Regex, String> {
+    if rule.pattern.is_empty() {
+        return Err("Replacement rules need text to match".to_string());
+    }
+    let source = match rule.mode.as_str() {
+        MODE_LITERAL => regex::escape(&rule.pattern),
+        MODE_WHOLE_WORD => {
+            // `\b` only means "word edge" next to a word character, so "C++" still matches.
+            let word_char =
+                |ch: Option<char>| ch.is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+            let start = if word_char(rule.pattern.chars().next()) {
+                r"\b"
+            } else {
+                ""
+            };
+            let end = if word_char(rule.pattern.chars().last()) {
+                r"\b"
+            } else {
+                ""
+            };
+            format!("{start}{}{end}", regex::escape(&rule.pattern))
+        }
+        MODE_REGEX => rule.pattern.clone(),
+        other => return Err(format!("Unknown replacement mode: {other}")),
+    };
+    RegexBuilder::new(&source)
+        .case_insensitive(!rule.case_sensitive)
+        .build()
+        .map_err(|err| format!("Invalid pattern \"{}\": {err}", rule.pattern))
+}
+
+/// Apply the enabled rules to `text` in order, each to the output of the one before. Returns
+/// the new text and the rules that changed it.
+pub fn apply(text: &str, rules: &[ReplacementRule]) -> (String, Vec<AppliedReplacement>) {
+    let mut text = text.to_string();
+    let mut applied = Vec::new();
+    for rule in rules.iter().filter(|rule| rule.enabled) {
+        let Ok(matcher) = compile(rule) else {
+            continue;
+        };
+        let mut count = 0_u32;
+        let replaced = matcher.replace_all(&text, |captures: &Captures| {
+            count += 1;
+            let mut value = String::new();
+            if rule.mode == MODE_REGEX {
+                captures.expand(&rule.replacement, &mut value);
+            } else {
+                value.push_str(&rule.replacement);
+            }
+            if rule.preserve_case {
+                match_case(&value, &captures[0])
+            } else {
+                value
+            }
+        });
+        if count > 0 {
+            text = replaced.into_owned();
+            applied.push(AppliedReplacement {
+                rule_id: rule.id.clone(),
+                pattern: rule.pattern.clone(),
+                replacement: rule.replacement.clone(),
+                count,
+            });
+        }
+    }
+    (text, applied)
+}
+
+/// Give `replacement` the casing of `matched`: all caps stays all caps and a capitalised match
+/// (at the start of a sentence, say) capitalises the replacement. Otherwise it is left as typed.
+fn match_case(replacement: &str, matched: &str) -> String {
+    let mut letters = matched.chars().filter(|ch| ch.is_alphabetic()).peekable();
+    let Some(first) = letters.peek().copied() else {
+        return replacement.to_string();
+    };
+    let all_upper = matched
+        .chars()
+        .filter(|ch| ch.is_alphabetic())
+        .all(char::is_uppercase);
+    if all_upper && letters.count() > 1 {
+        return replacement.to_uppercase();
+    }
+    if first.is_uppercase() {
+        let mut chars = replacement.chars();
+        if let Some(head) = chars.next() {
+            return head.to_uppercase().chain(chars).collect();
+        }
+    }
+    replacement.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, replacement: &str, mode: &str) -> ReplacementRule {
+        ReplacementRule {
+            id: pattern.to_string(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            mode: mode.to_string(),
+            case_sensitive: false,
+            preserve_case: false,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn whole_word_rules_leave_longer_words_alone() {
+        let rules = vec![
+            rule("cube control", "kubectl", MODE_WHOLE_WORD),
+            rule("postgrest", "PostgREST", MODE_WHOLE_WORD),
+        ];
+        let (text, applied) = apply(
+            "Run Cube Control against PostgREST, not postgresting.",
+            &rules,
+        );
+        assert_eq!(text, "Run kubectl against PostgREST, not postgresting.");
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].rule_id, "cube control");
+        assert_eq!(applied[0].count, 1);
+
+        let (text, _) = apply("Use c++ here", &[rule("c++", "C++", MODE_WHOLE_WORD)]);
+        assert_eq!(text, "Use C++ here");
+    }
+
+    #[test]
+    fn literal_and_regex_rules() {
+        let (text, applied) = apply("k8s and K8S", &[rule("k8s", "Kubernetes", MODE_LITERAL)]);
+        assert_eq!(text, "Kubernetes and Kubernetes");
+        assert_eq!(applied[0].count, 2);
+
+        let (text, _) = apply(
+            "ticket abc 123 and abc 45",
+            &[rule(r"\babc (\d+)", "ABC-$1", MODE_REGEX)],
+        );
+        assert_eq!(text, "ticket ABC-123 and ABC-45");
+
+        let mut sensitive = rule("Go", "Golang", MODE_WHOLE_WORD);
+        sensitive.case_sensitive = true;
+        let (text, _) = apply("Go where you go", &[sensitive]);
+        assert_eq!(text, "Golang where you go");
+    }
+
+    #[test]
+    fn preserve_case_follows_the_match() {
+        let mut adapt = rule("colour", "color", MODE_WHOLE_WORD);
+        adapt.preserve_case = true;
+        let (text, _) = apply("Colour, colour and COLOUR", &[adapt]);
+        assert_eq!(text, "Color, color and COLOR");
+    }
+
+    #[test]
+    fn disabled_and_invalid_rules_are_skipped() {
+        let mut disabled = rule("one", "1", MODE_LITERAL);
+        disabled.enabled = false;
+        let invalid = rule("(", "x", MODE_REGEX);
+        assert!(compile(&invalid).is_err());
+        let (text, applied) = apply("one (", &[disabled, invalid]);
+        assert_eq!(text, "one (");
+        assert!(applied.is_empty());
+    }
+}
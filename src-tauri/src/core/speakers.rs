@@ -0,0 +1,381 @@
+use crate::types::TranscriptSegment;
+
+/// Analysis frame for the spectral features.
+const FRAME_MS: usize = 20;
+/// Band-pass centres, log-spaced across the range that carries most voice timbre.
+const BAND_LOW_HZ: f32 = 150.0;
+const BAND_HIGH_HZ: f32 = 5_000.0;
+const BAND_COUNT: usize = 12;
+const BAND_Q: f32 = 2.0;
+/// Groups shorter than this are too short to characterise and inherit a neighbour's speaker.
+const MIN_GROUP_MS: i64 = 500;
+/// Average-linkage clusters closer than this (RMS difference of the spectral envelopes, in dB)
+/// are merged into one speaker.
+const SPEAKER_DISTANCE_DB: f32 = 4.0;
+const MAX_SPEAKERS: usize = 8;
+
+/// Label each segment with a 0-based speaker index, numbered by first appearance.
+///
+/// Segments arrive either with `speaker` set to a tinydiarize turn number (consecutive segments
+/// with the same number belong to one turn) or with `speaker` unset, in which case each segment
+/// is its own turn. Turns are then clustered by their average spectral envelope, so a speaker who
+/// comes back later gets the same label. Timestamps are relative to `samples`.
+pub fn assign_speakers(segments: &mut [TranscriptSegment], samples: &[f32], rate: u32) {
+    if segments.is_empty() {
+        return;
+    }
+    let turns = group_turns(segments);
+    let frames = frame_envelopes(samples, rate);
+    let frame_ms = FRAME_MS as i64;
+
+    let features: Vec<Option<Vec<f32>>> = turns
+        .iter()
+        .map(|turn| {
+            let start_ms = segments[turn.start].start_ms;
+            let end_ms = segments[turn.end - 1].end_ms;
+            if end_ms - start_ms < MIN_GROUP_MS {
+                return None;
+            }
+            let first = (start_ms / frame_ms).max(0) as usize;
+            let last = ((end_ms / frame_ms).max(0) as usize).min(frames.len());
+            turn_envelope(frames.get(first..last)?)
+        })
+        .collect();
+
+    let clustered: Vec<usize> = (0..turns.len())
+        .filter(|&index| features[index].is_some())
+        .collect();
+    let vectors: Vec<&[f32]> = clustered
+        .iter()
+        .filter_map(|&index| features[index].as_deref())
+        .collect();
+    let clusters = cluster(&vectors, SPEAKER_DISTANCE_DB, MAX_SPEAKERS);
+
+    let mut labels: Vec<Option<usize>> = vec![None; turns.len()];
+    for (position, &index) in clustered.iter().enumerate() {
+        labels[index] = Some(clusters[position]);
+    }
+    // Short turns take the previous speaker, or the next one at the very start.
+    let mut previous = labels.iter().flatten().next().copied().unwrap_or(0);
+    for label in labels.iter_mut() {
+        match label {
+            Some(value) => previous = *value,
+            None => *label = Some(previous),
+        }
+    }
+
+    let mut order: Vec<usize> = Vec::new();
+    for (turn, label) in turns.iter().zip(labels) {
+        let label = label.unwrap_or(0);
+        let speaker = match order.iter().position(|&seen| seen == label) {
+            Some(position) => position,
+            None => {
+                order.push(label);
+                order.len() - 1
+            }
+        };
+        for segment in &mut segments[turn.clone()] {
+            segment.speaker = Some(speaker as u32);
+        }
+    }
+}
+
+/// Runs of consecutive segments with the same tinydiarize turn number (or single segments when
+/// there are none).
+fn group_turns(segments: &[TranscriptSegment]) -> Vec<std::ops::Range<usize>> {
+    let mut turns = Vec::new();
+    let mut start = 0;
+    for index in 1..=segments.len() {
+        let boundary = index == segments.len()
+            || segments[index].speaker.is_none()
+            || segments[index].speaker != segments[start].speaker;
+        if boundary {
+            turns.push(start..index);
+            start = index;
+        }
+    }
+    turns
+}
+
+/// Per-frame log band energies (dB), with each frame's mean removed so loudness doesn't count.
+/// The last value of each frame is the frame's overall level, kept to find the voiced frames.
+fn frame_envelopes(samples: &[f32], rate: u32) -> Vec<Vec<f32>> {
+    let frame_len = (FRAME_MS * rate as usize / 1000).max(1);
+    let frame_count = samples.len() / frame_len;
+
+    // Band-major energies: `bands[band][frame]`.
+    let bands: Vec<Vec<f32>> = (0..BAND_COUNT)
+        .map(|band| {
+            let ratio = (BAND_HIGH_HZ / BAND_LOW_HZ).powf(band as f32 / (BAND_COUNT - 1) as f32);
+            let centre = (BAND_LOW_HZ * ratio).min(rate as f32 * 0.45);
+            let mut filter = BandPass::new(centre, rate as f32);
+            samples
+                .chunks_exact(frame_len)
+                .map(|chunk| {
+                    chunk
+                        .iter()
+                        .map(|sample| {
+                            let value = filter.process(*sample);
+                            value * value
+                        })
+                        .sum::<f32>()
+                })
+                .collect()
+        })
+        .collect();
+
+    (0..frame_count)
+        .map(|frame| {
+            let mut db: Vec<f32> = bands
+                .iter()
+                .map(|energies| 10.0 * (energies[frame] + 1e-10).log10())
+                .collect();
+            let level = db.iter().sum::<f32>() / BAND_COUNT as f32;
+            for value in db.iter_mut() {
+                *value -= level;
+            }
+            db.push(level);
+            db
+        })
+        .collect()
+}
+
+/// Average envelope over the louder half of a turn's frames, where the voice is.
+fn turn_envelope(frames: &[Vec<f32>]) -> Option<Vec<f32>> {
+    if frames.is_empty() {
+        return None;
+    }
+    let mut levels: Vec<f32> = frames.iter().map(|frame| frame[BAND_COUNT]).collect();
+    levels.sort_by(|a, b| a.total_cmp(b));
+    let median = levels[levels.len() / 2];
+
+    let mut sum = vec![0.0_f32; BAND_COUNT];
+    let mut count = 0;
+    for frame in frames.iter().filter(|frame| frame[BAND_COUNT] >= median) {
+        for (total, value) in sum.iter_mut().zip(frame) {
+            *total += value;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return None;
+    }
+    Some(sum.into_iter().map(|total| total / count as f32).collect())
+}
+
+/// Average-linkage agglomerative clustering; returns a cluster index per vector.
+///
+/// Distances between clusters are updated with the Lance–Williams formula as they merge, and each
+/// cluster remembers its nearest neighbour, so an hour of single-segment turns stays quick.
+fn cluster(vectors: &[&[f32]], threshold: f32, max_clusters: usize) -> Vec<usize> {
+    let count = vectors.len();
+    let mut distance = vec![0.0_f32; count * count];
+    for i in 0..count {
+        for j in i + 1..count {
+            let value = rms_distance(vectors[i], vectors[j]);
+            distance[i * count + j] = value;
+            distance[j * count + i] = value;
+        }
+    }
+    let mut members: Vec<Vec<usize>> = (0..count).map(|index| vec![index]).collect();
+    let mut active = vec![true; count];
+    let closest = |i: usize, active: &[bool], distance: &[f32]| {
+        (0..count)
+            .filter(|&k| k != i && active[k])
+            .map(|k| (distance[i * count + k], k))
+            .fold(
+                (f32::MAX, i),
+                |best, next| if next.0 < best.0 { next } else { best },
+            )
+    };
+    let mut nearest: Vec<(f32, usize)> =
+        (0..count).map(|i| closest(i, &active, &distance)).collect();
+
+    let mut remaining = count;
+    while remaining > 1 {
+        let Some(i) = (0..count)
+            .filter(|&i| active[i])
+            .min_by(|&a, &b| nearest[a].0.total_cmp(&nearest[b].0))
+        else {
+            break;
+        };
+        let (best, j) = nearest[i];
+        if best > threshold && remaining <= max_clusters {
+            break;
+        }
+        let (keep, gone) = (i.min(j), i.max(j));
+        let (keep_size, gone_size) = (members[keep].len() as f32, members[gone].len() as f32);
+        for k in (0..count).filter(|&k| active[k] && k != keep && k != gone) {
+            let merged = (keep_size * distance[k * count + keep]
+                + gone_size * distance[k * count + gone])
+                / (keep_size + gone_size);
+            distance[k * count + keep] = merged;
+            distance[keep * count + k] = merged;
+        }
+        let moved = std::mem::take(&mut members[gone]);
+        members[keep].extend(moved);
+        active[gone] = false;
+        remaining -= 1;
+
+        nearest[keep] = closest(keep, &active, &distance);
+        for k in (0..count).filter(|&k| active[k] && k != keep) {
+            let to_keep = distance[k * count + keep];
+            if nearest[k].1 == keep || nearest[k].1 == gone {
+                // The old neighbour moved away or no longer exists.
+                nearest[k] = closest(k, &active, &distance);
+            } else if to_keep < nearest[k].0 {
+                nearest[k] = (to_keep, keep);
+            }
+        }
+    }
+
+    let mut assignment = vec![0; count];
+    for (label, cluster) in members
+        .iter()
+        .filter(|cluster| !cluster.is_empty())
+        .enumerate()
+    {
+        for &member in cluster {
+            assignment[member] = label;
+        }
+    }
+    assignment
+}
+
+fn rms_distance(a: &[f32], b: &[f32]) -> f32 {
+    let sum: f32 = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum();
+    (sum / a.len().max(1) as f32).sqrt()
+}
+
+/// RBJ constant-peak band-pass biquad.
+struct BandPass {
+    b0: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl BandPass {
+    fn new(centre: f32, rate: f32) -> Self {
+        let w0 = 2.0 * std::f32::consts::PI * centre / rate;
+        let alpha = w0.sin() / (2.0 * BAND_Q);
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: alpha / a0,
+            b2: -alpha / a0,
+            a1: -2.0 * w0.cos() / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.b0 * x + self.b2 * self.x2 - self.a1 * self.y1 - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    /// A glottal-like pulse train at `pitch` Hz through a one-pole filter: positive `tilt` darkens
+    /// the spectrum, negative brightens it.
+    fn voice(seconds: f32, pitch: f32, tilt: f32, gain: f32) -> Vec<f32> {
+        let period = (RATE as f32 / pitch) as usize;
+        let mut seed = 7_u32;
+        let mut previous = 0.0;
+        (0..(seconds * RATE as f32) as usize)
+            .map(|n| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (seed >> 8) as f32 / (1 << 24) as f32 - 0.5;
+                let x = if n % period == 0 { 1.0 } else { 0.0 } + noise * 0.02;
+                let y = x + tilt * previous;
+                previous = y;
+                y * gain * 0.2
+            })
+            .collect()
+    }
+
+    fn segments(bounds: &[(i64, i64)]) -> Vec<TranscriptSegment> {
+        bounds
+            .iter()
+            .map(|&(start_ms, end_ms)| TranscriptSegment {
+                start_ms,
+                end_ms,
+                text: "words".to_string(),
+                speaker: None,
+            })
+            .collect()
+    }
+
+    fn speakers(segments: &[TranscriptSegment]) -> Vec<u32> {
+        segments
+            .iter()
+            .map(|segment| segment.speaker.unwrap_or(u32::MAX))
+            .collect()
+    }
+
+    #[test]
+    fn separates_alternating_voices() {
+        let mut audio = Vec::new();
+        for turn in 0..4 {
+            if turn % 2 == 0 {
+                audio.extend(voice(1.5, 110.0, 0.9, 1.0 + turn as f32 * 0.2));
+            } else {
+                audio.extend(voice(1.5, 210.0, -0.8, 0.5));
+            }
+        }
+        let mut segments = segments(&[(0, 1_500), (1_500, 3_000), (3_000, 4_500), (4_500, 6_000)]);
+
+        assign_speakers(&mut segments, &audio, RATE);
+        assert_eq!(speakers(&segments), vec![0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn one_voice_at_different_levels_is_one_speaker() {
+        let mut audio = voice(1.5, 120.0, 0.85, 1.0);
+        audio.extend(voice(1.5, 120.0, 0.85, 0.3));
+        audio.extend(voice(1.5, 120.0, 0.85, 2.0));
+        let mut segments = segments(&[(0, 1_500), (1_500, 3_000), (3_000, 4_500)]);
+
+        assign_speakers(&mut segments, &audio, RATE);
+        assert_eq!(speakers(&segments), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn clusters_merge_until_far_apart_or_within_the_limit() {
+        let points = [[0.0_f32], [1.0], [10.0], [11.5], [30.0]];
+        let vectors: Vec<&[f32]> = points.iter().map(|point| point.as_slice()).collect();
+        assert_eq!(cluster(&vectors, 2.0, 8), vec![0, 0, 1, 1, 2]);
+        // Average linkage: {0, 1} is 10.25 from {10, 11.5}, closer than 30 is to either.
+        assert_eq!(cluster(&vectors, 2.0, 2), vec![0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn tinydiarize_turns_and_short_segments_keep_their_neighbours_speaker() {
+        let mut audio = voice(2.0, 110.0, 0.9, 1.0);
+        audio.extend(voice(2.0, 210.0, -0.8, 0.5));
+        // Turn numbers from tinydiarize: the first two segments are one turn.
+        let mut segments = segments(&[(0, 1_200), (1_200, 2_000), (2_000, 3_800), (3_800, 4_000)]);
+        segments[0].speaker = Some(0);
+        segments[1].speaker = Some(0);
+        segments[2].speaker = Some(1);
+
+        assign_speakers(&mut segments, &audio, RATE);
+        assert_eq!(speakers(&segments), vec![0, 0, 1, 1]);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -6,10 +6,16 @@ use hound::{SampleFormat, WavSpec, WavWriter};
 use rusqlite::{params, Connection, OptionalExtension};
 use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
+use uuid::Uuid;
 
 use crate::core::audio::RecordedAudio;
+use crate::core::dictation;
 use crate::settings::Settings;
-use crate::types::{Clip, Transcript};
+use crate::types::{
+    AppliedReplacement, BenchmarkResult, Clip, DictationCommand, EvaluationFile, EvaluationResult,
+    FilteredSegment, LanguageProbability, ReplacementRule, Transcript, TranscriptSegment,
+    TranscriptWord, VocabSuggestion,
+};
 
 const DB_FILE: &str = "whispr.db";
 const LEGACY_SETTINGS_FILE: &str = "settings.json";
@@ -27,6 +33,48 @@ fn encode_tags(tags: &[String]) -> Result<Option<String>, String> {
     }
 }
 
+fn encode_language_probabilities(
+    probabilities: &[LanguageProbability],
+) -> Result<Option<String>, String> {
+    if probabilities.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(
+            serde_json::to_string(probabilities).map_err(|err| err.to_string())?,
+        ))
+    }
+}
+
+fn encode_filtered(filtered: &[FilteredSegment]) -> Result<Option<String>, String> {
+    if filtered.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(
+            serde_json::to_string(filtered).map_err(|err| err.to_string())?,
+        ))
+    }
+}
+
+fn encode_replacements(replacements: &[AppliedReplacement]) -> Result<Option<String>, String> {
+    if replacements.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(
+            serde_json::to_string(replacements).map_err(|err| err.to_string())?,
+        ))
+    }
+}
+
+fn encode_vocab_suggestions(suggestions: &[VocabSuggestion]) -> Result<Option<String>, String> {
+    if suggestions.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(
+            serde_json::to_string(suggestions).map_err(|err| err.to_string())?,
+        ))
+    }
+}
+
 fn encode_embedding(embedding: &Option<Vec<f32>>) -> Result<Option<String>, String> {
     embedding
         .as_ref()
@@ -78,6 +126,25 @@ pub fn audio_path(settings: &Settings, transcript_id: &str) -> PathBuf {
     audio_dir(settings).join(format!("{transcript_id}.wav"))
 }
 
+/// Where a long recording is written while it is captured.
+pub fn spool_path(settings: &Settings, started_at_ms: i64) -> PathBuf {
+    data_dir(settings)
+        .join("spool")
+        .join(format!("{started_at_ms}.wav"))
+}
+
+/// Keep a spooled recording as the transcript's audio instead of re-encoding it.
+pub fn keep_spooled_audio(
+    settings: &Settings,
+    transcript_id: &str,
+    spool: &Path,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(audio_dir(settings)).map_err(|err| err.to_string())?;
+    let path = audio_path(settings, transcript_id);
+    fs::rename(spool, &path).map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
 pub fn save_audio_recording(
     settings: &Settings,
     transcript_id: &str,
@@ -143,6 +210,12 @@ fn ensure_schema(conn: &Connection) -> Result<(), String> {
         duration_ms INTEGER NOT NULL,
         text TEXT NOT NULL,
         language TEXT,
+        language_probs TEXT,
+        translated INTEGER NOT NULL DEFAULT 0,
+        filtered TEXT,
+        speaker_names TEXT,
+        replacements TEXT,
+        vocab_suggestions TEXT,
         tags TEXT,
         title TEXT,
         summary TEXT,
@@ -150,16 +223,122 @@ fn ensure_schema(conn: &Connection) -> Result<(), String> {
         audio_path TEXT,
         source TEXT NOT NULL
       );
+      CREATE TABLE IF NOT EXISTS transcript_segments (
+        transcript_id TEXT NOT NULL,
+        idx INTEGER NOT NULL,
+        start_ms INTEGER NOT NULL,
+        end_ms INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        speaker INTEGER,
+        PRIMARY KEY (transcript_id, idx)
+      );
+      CREATE TABLE IF NOT EXISTS transcript_words (
+        transcript_id TEXT NOT NULL,
+        idx INTEGER NOT NULL,
+        start_ms INTEGER NOT NULL,
+        end_ms INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        confidence REAL NOT NULL,
+        PRIMARY KEY (transcript_id, idx)
+      );
       CREATE TABLE IF NOT EXISTS clips (
         id TEXT PRIMARY KEY,
         created_at INTEGER NOT NULL,
         title TEXT NOT NULL,
         text TEXT NOT NULL,
         transcript_id TEXT
+      );
+      CREATE TABLE IF NOT EXISTS benchmarks (
+        id TEXT PRIMARY KEY,
+        run_id TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        model TEXT NOT NULL,
+        threads INTEGER NOT NULL,
+        gpu INTEGER NOT NULL,
+        cold_load_ms INTEGER NOT NULL,
+        audio_seconds REAL NOT NULL,
+        duration_ms INTEGER NOT NULL,
+        peak_memory_bytes INTEGER,
+        gpu_name TEXT,
+        cpu_count INTEGER NOT NULL
+      );
+      CREATE TABLE IF NOT EXISTS evaluations (
+        id TEXT PRIMARY KEY,
+        run_id TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        model TEXT NOT NULL,
+        preset TEXT NOT NULL,
+        custom_vocab TEXT NOT NULL,
+        wer REAL NOT NULL,
+        cer REAL NOT NULL,
+        substitutions INTEGER NOT NULL,
+        deletions INTEGER NOT NULL,
+        insertions INTEGER NOT NULL,
+        reference_words INTEGER NOT NULL,
+        audio_seconds REAL NOT NULL,
+        duration_ms INTEGER NOT NULL,
+        files TEXT NOT NULL
+      );
+      CREATE TABLE IF NOT EXISTS replacement_rules (
+        id TEXT PRIMARY KEY,
+        pattern TEXT NOT NULL,
+        replacement TEXT NOT NULL,
+        mode TEXT NOT NULL,
+        case_sensitive INTEGER NOT NULL DEFAULT 0,
+        preserve_case INTEGER NOT NULL DEFAULT 0,
+        enabled INTEGER NOT NULL DEFAULT 1
       );",
     )
     .map_err(|err| err.to_string())
     .and_then(|_| ensure_transcript_columns(conn))
+    .and_then(|_| ensure_dictation_commands(conn))
+}
+
+/// Create the dictation command table, seeded with the default commands the first time.
+fn ensure_dictation_commands(conn: &Connection) -> Result<(), String> {
+    let exists = conn
+        .prepare("SELECT 1 FROM dictation_commands LIMIT 0")
+        .is_ok();
+    if exists {
+        return Ok(());
+    }
+    conn.execute(
+        "CREATE TABLE dictation_commands (
+        id TEXT PRIMARY KEY,
+        language TEXT NOT NULL,
+        phrase TEXT NOT NULL,
+        action TEXT NOT NULL,
+        value TEXT NOT NULL DEFAULT ''
+      )",
+        [],
+    )
+    .map_err(|err| err.to_string())?;
+    for mut command in dictation::default_commands() {
+        command.id = Uuid::new_v4().to_string();
+        insert_dictation_command(conn, &command)?;
+    }
+    Ok(())
+}
+
+fn insert_dictation_command(conn: &Connection, command: &DictationCommand) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO dictation_commands (id, language, phrase, action, value)
+       VALUES (?1, ?2, ?3, ?4, ?5)
+       ON CONFLICT(id) DO UPDATE SET
+         language = excluded.language,
+         phrase = excluded.phrase,
+         action = excluded.action,
+         value = excluded.value",
+        params![
+            command.id,
+            command.language,
+            command.phrase,
+            command.action,
+            command.value,
+        ],
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
 }
 
 fn ensure_transcript_columns(conn: &Connection) -> Result<(), String> {
@@ -189,10 +368,149 @@ fn ensure_transcript_columns(conn: &Connection) -> Result<(), String> {
     add_column("summary", "summary TEXT")?;
     add_column("embedding", "embedding TEXT")?;
     add_column("audio_path", "audio_path TEXT")?;
+    add_column("language_probs", "language_probs TEXT")?;
+    add_column("translated", "translated INTEGER NOT NULL DEFAULT 0")?;
+    add_column("filtered", "filtered TEXT")?;
+    add_column("speaker_names", "speaker_names TEXT")?;
+    add_column("replacements", "replacements TEXT")?;
+    add_column("vocab_suggestions", "vocab_suggestions TEXT")?;
+
+    let has_speaker = conn
+        .prepare("SELECT speaker FROM transcript_segments LIMIT 0")
+        .is_ok();
+    if !has_speaker {
+        conn.execute(
+            "ALTER TABLE transcript_segments ADD COLUMN speaker INTEGER",
+            [],
+        )
+        .map_err(|err| err.to_string())?;
+    }
 
     Ok(())
 }
 
+fn write_segments(conn: &Connection, transcript: &Transcript) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM transcript_segments WHERE transcript_id = ?1",
+        params![transcript.id],
+    )
+    .map_err(|err| err.to_string())?;
+
+    if transcript.segments.is_empty() {
+        return Ok(());
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "INSERT INTO transcript_segments (transcript_id, idx, start_ms, end_ms, text, speaker)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .map_err(|err| err.to_string())?;
+    for (index, segment) in transcript.segments.iter().enumerate() {
+        stmt.execute(params![
+            transcript.id,
+            index as i64,
+            segment.start_ms,
+            segment.end_ms,
+            segment.text,
+            segment.speaker,
+        ])
+        .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn load_segments(conn: &Connection) -> Result<HashMap<String, Vec<TranscriptSegment>>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT transcript_id, start_ms, end_ms, text, speaker
+       FROM transcript_segments
+       ORDER BY transcript_id, idx",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            let transcript_id: String = row.get(0)?;
+            Ok((
+                transcript_id,
+                TranscriptSegment {
+                    start_ms: row.get(1)?,
+                    end_ms: row.get(2)?,
+                    text: row.get(3)?,
+                    speaker: row.get(4)?,
+                },
+            ))
+        })
+        .map_err(|err| err.to_string())?;
+
+    let mut segments: HashMap<String, Vec<TranscriptSegment>> = HashMap::new();
+    for (transcript_id, segment) in rows.flatten() {
+        segments.entry(transcript_id).or_default().push(segment);
+    }
+    Ok(segments)
+}
+
+fn write_words(conn: &Connection, transcript: &Transcript) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM transcript_words WHERE transcript_id = ?1",
+        params![transcript.id],
+    )
+    .map_err(|err| err.to_string())?;
+
+    if transcript.words.is_empty() {
+        return Ok(());
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "INSERT INTO transcript_words (transcript_id, idx, start_ms, end_ms, text, confidence)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .map_err(|err| err.to_string())?;
+    for (index, word) in transcript.words.iter().enumerate() {
+        stmt.execute(params![
+            transcript.id,
+            index as i64,
+            word.start_ms,
+            word.end_ms,
+            word.text,
+            word.confidence as f64,
+        ])
+        .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn load_words(conn: &Connection) -> Result<HashMap<String, Vec<TranscriptWord>>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT transcript_id, start_ms, end_ms, text, confidence
+       FROM transcript_words
+       ORDER BY transcript_id, idx",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            let transcript_id: String = row.get(0)?;
+            Ok((
+                transcript_id,
+                TranscriptWord {
+                    start_ms: row.get(1)?,
+                    end_ms: row.get(2)?,
+                    text: row.get(3)?,
+                    confidence: row.get::<_, f64>(4)? as f32,
+                },
+            ))
+        })
+        .map_err(|err| err.to_string())?;
+
+    let mut words: HashMap<String, Vec<TranscriptWord>> = HashMap::new();
+    for (transcript_id, word) in rows.flatten() {
+        words.entry(transcript_id).or_default().push(word);
+    }
+    Ok(words)
+}
+
 fn settings_entries(settings: &Settings) -> Vec<(&'static str, Value)> {
     vec![
         (
@@ -214,12 +532,46 @@ fn settings_entries(settings: &Settings) -> Vec<(&'static str, Value)> {
         ("audio.vad_threshold", json!(settings.audio.vad_threshold)),
         ("audio.vad_silence_ms", json!(settings.audio.vad_silence_ms)),
         ("audio.vad_resume_ms", json!(settings.audio.vad_resume_ms)),
+        ("audio.long_recording", json!(settings.audio.long_recording)),
+        ("audio.auto_stop", json!(settings.audio.auto_stop)),
+        (
+            "audio.auto_stop_silence_ms",
+            json!(settings.audio.auto_stop_silence_ms),
+        ),
+        (
+            "audio.auto_stop_no_speech_ms",
+            json!(settings.audio.auto_stop_no_speech_ms),
+        ),
+        ("audio.pre_roll", json!(settings.audio.pre_roll)),
+        ("audio.pre_roll_ms", json!(settings.audio.pre_roll_ms)),
         (
             "hotkey.record_toggle",
             json!(settings.hotkeys.record_toggle),
         ),
         ("hotkey.paste_last", json!(settings.hotkeys.paste_last)),
         ("hotkey.open_app", json!(settings.hotkeys.open_app)),
+        (
+            "hotkey.record_translate",
+            json!(settings.hotkeys.record_translate),
+        ),
+        ("hotkey.pause_toggle", json!(settings.hotkeys.pause_toggle)),
+        ("hotkey.push_to_talk", json!(settings.hotkeys.push_to_talk)),
+        (
+            "hotkey.push_to_talk_min_hold_ms",
+            json!(settings.hotkeys.push_to_talk_min_hold_ms),
+        ),
+        (
+            "transcription.backend",
+            json!(settings.transcription.backend),
+        ),
+        (
+            "transcription.server_url",
+            json!(settings.transcription.server_url),
+        ),
+        (
+            "transcription.server_model",
+            json!(settings.transcription.server_model),
+        ),
         ("transcription.model", json!(settings.transcription.model)),
         (
             "transcription.model_dir",
@@ -233,14 +585,86 @@ fn settings_entries(settings: &Settings) -> Vec<(&'static str, Value)> {
             "transcription.language",
             json!(settings.transcription.language),
         ),
+        (
+            "transcription.translate",
+            json!(settings.transcription.translate),
+        ),
         (
             "transcription.custom_vocab",
             json!(settings.transcription.custom_vocab),
         ),
+        (
+            "transcription.vocab_correction",
+            json!(settings.transcription.vocab_correction),
+        ),
+        (
+            "transcription.vocab_correction_threshold",
+            json!(settings.transcription.vocab_correction_threshold),
+        ),
         (
             "transcription.use_gpu",
             json!(settings.transcription.use_gpu),
         ),
+        (
+            "transcription.context_cache_mb",
+            json!(settings.transcription.context_cache_mb),
+        ),
+        (
+            "transcription.decoding_preset",
+            json!(settings.transcription.decoding_preset),
+        ),
+        (
+            "transcription.beam_size",
+            json!(settings.transcription.beam_size),
+        ),
+        (
+            "transcription.beam_patience",
+            json!(settings.transcription.beam_patience),
+        ),
+        (
+            "transcription.temperature",
+            json!(settings.transcription.temperature),
+        ),
+        (
+            "transcription.temperature_increment",
+            json!(settings.transcription.temperature_increment),
+        ),
+        (
+            "transcription.entropy_threshold",
+            json!(settings.transcription.entropy_threshold),
+        ),
+        (
+            "transcription.logprob_threshold",
+            json!(settings.transcription.logprob_threshold),
+        ),
+        (
+            "transcription.no_speech_threshold",
+            json!(settings.transcription.no_speech_threshold),
+        ),
+        (
+            "transcription.filter_hallucinations",
+            json!(settings.transcription.filter_hallucinations),
+        ),
+        (
+            "transcription.detect_speakers",
+            json!(settings.transcription.detect_speakers),
+        ),
+        (
+            "transcription.dictation_languages",
+            json!(settings.transcription.dictation_languages),
+        ),
+        (
+            "transcription.inverse_text_normalization",
+            json!(settings.transcription.inverse_text_normalization),
+        ),
+        (
+            "transcription.continuation_context",
+            json!(settings.transcription.continuation_context),
+        ),
+        (
+            "transcription.continuation_window_secs",
+            json!(settings.transcription.continuation_window_secs),
+        ),
         (
             "automation.auto_paste_enabled",
             json!(settings.automation.auto_paste_enabled),
@@ -299,15 +723,78 @@ fn apply_setting(settings: &mut Settings, key: &str, value: Value) {
         "audio.vad_threshold" => assign(&mut settings.audio.vad_threshold, value),
         "audio.vad_silence_ms" => assign(&mut settings.audio.vad_silence_ms, value),
         "audio.vad_resume_ms" => assign(&mut settings.audio.vad_resume_ms, value),
+        "audio.long_recording" => assign(&mut settings.audio.long_recording, value),
+        "audio.auto_stop" => assign(&mut settings.audio.auto_stop, value),
+        "audio.auto_stop_silence_ms" => assign(&mut settings.audio.auto_stop_silence_ms, value),
+        "audio.auto_stop_no_speech_ms" => assign(&mut settings.audio.auto_stop_no_speech_ms, value),
+        "audio.pre_roll" => assign(&mut settings.audio.pre_roll, value),
+        "audio.pre_roll_ms" => assign(&mut settings.audio.pre_roll_ms, value),
         "hotkey.record_toggle" => assign(&mut settings.hotkeys.record_toggle, value),
         "hotkey.paste_last" => assign(&mut settings.hotkeys.paste_last, value),
         "hotkey.open_app" => assign(&mut settings.hotkeys.open_app, value),
+        "hotkey.record_translate" => assign(&mut settings.hotkeys.record_translate, value),
+        "hotkey.pause_toggle" => assign(&mut settings.hotkeys.pause_toggle, value),
+        "hotkey.push_to_talk" => assign(&mut settings.hotkeys.push_to_talk, value),
+        "hotkey.push_to_talk_min_hold_ms" => {
+            assign(&mut settings.hotkeys.push_to_talk_min_hold_ms, value)
+        }
+        "transcription.backend" => assign(&mut settings.transcription.backend, value),
+        "transcription.server_url" => assign(&mut settings.transcription.server_url, value),
+        "transcription.server_model" => assign(&mut settings.transcription.server_model, value),
         "transcription.model" => assign(&mut settings.transcription.model, value),
         "transcription.model_dir" => assign(&mut settings.transcription.model_dir, value),
         "transcription.threads" => assign(&mut settings.transcription.threads, value),
         "transcription.language" => assign(&mut settings.transcription.language, value),
+        "transcription.translate" => assign(&mut settings.transcription.translate, value),
         "transcription.custom_vocab" => assign(&mut settings.transcription.custom_vocab, value),
+        "transcription.vocab_correction" => {
+            assign(&mut settings.transcription.vocab_correction, value)
+        }
+        "transcription.vocab_correction_threshold" => assign(
+            &mut settings.transcription.vocab_correction_threshold,
+            value,
+        ),
         "transcription.use_gpu" => assign(&mut settings.transcription.use_gpu, value),
+        "transcription.context_cache_mb" => {
+            assign(&mut settings.transcription.context_cache_mb, value)
+        }
+        "transcription.decoding_preset" => {
+            assign(&mut settings.transcription.decoding_preset, value)
+        }
+        "transcription.beam_size" => assign(&mut settings.transcription.beam_size, value),
+        "transcription.beam_patience" => assign(&mut settings.transcription.beam_patience, value),
+        "transcription.temperature" => assign(&mut settings.transcription.temperature, value),
+        "transcription.temperature_increment" => {
+            assign(&mut settings.transcription.temperature_increment, value)
+        }
+        "transcription.entropy_threshold" => {
+            assign(&mut settings.transcription.entropy_threshold, value)
+        }
+        "transcription.logprob_threshold" => {
+            assign(&mut settings.transcription.logprob_threshold, value)
+        }
+        "transcription.no_speech_threshold" => {
+            assign(&mut settings.transcription.no_speech_threshold, value)
+        }
+        "transcription.filter_hallucinations" => {
+            assign(&mut settings.transcription.filter_hallucinations, value)
+        }
+        "transcription.detect_speakers" => {
+            assign(&mut settings.transcription.detect_speakers, value)
+        }
+        "transcription.dictation_languages" => {
+            assign(&mut settings.transcription.dictation_languages, value)
+        }
+        "transcription.inverse_text_normalization" => assign(
+            &mut settings.transcription.inverse_text_normalization,
+            value,
+        ),
+        "transcription.continuation_context" => {
+            assign(&mut settings.transcription.continuation_context, value)
+        }
+        "transcription.continuation_window_secs" => {
+            assign(&mut settings.transcription.continuation_window_secs, value)
+        }
         "automation.auto_paste_enabled" => {
             assign(&mut settings.automation.auto_paste_enabled, value)
         }
@@ -421,33 +908,30 @@ fn maybe_migrate_legacy_transcripts(
         Err(_) => return Ok(()),
     };
 
-    save_transcripts_to_conn(conn, settings, &transcripts)
+    save_transcripts_to_conn(conn, &transcripts)
 }
 
 fn save_transcripts_to_conn(
     conn: &mut Connection,
-    settings: &Settings,
     transcripts: &[Transcript],
 ) -> Result<(), String> {
     let tx = conn.transaction().map_err(|err| err.to_string())?;
     tx.execute("DELETE FROM transcripts", [])
         .map_err(|err| err.to_string())?;
+    tx.execute("DELETE FROM transcript_segments", [])
+        .map_err(|err| err.to_string())?;
+    tx.execute("DELETE FROM transcript_words", [])
+        .map_err(|err| err.to_string())?;
 
     {
         let mut stmt = tx
       .prepare(
         "INSERT INTO transcripts
-          (id, created_at, duration_ms, text, language, tags, title, summary, embedding, audio_path, source)
-          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+          (id, created_at, duration_ms, text, language, language_probs, translated, filtered, speaker_names, replacements, vocab_suggestions, tags, title, summary, embedding, audio_path, source)
+          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
       )
       .map_err(|err| err.to_string())?;
 
-        let language = if settings.transcription.language.is_empty() {
-            None
-        } else {
-            Some(settings.transcription.language.as_str())
-        };
-
         for transcript in transcripts {
             let tags = if transcript.tags.is_empty() {
                 None
@@ -460,13 +944,24 @@ fn save_transcripts_to_conn(
                 .map(serde_json::to_string)
                 .transpose()
                 .map_err(|err| err.to_string())?;
+            let language_probs = encode_language_probabilities(&transcript.language_probabilities)?;
+            let filtered = encode_filtered(&transcript.filtered)?;
+            let speaker_names = encode_tags(&transcript.speaker_names)?;
+            let replacements = encode_replacements(&transcript.replacements)?;
+            let vocab_suggestions = encode_vocab_suggestions(&transcript.vocab_suggestions)?;
 
             stmt.execute(params![
                 transcript.id,
                 transcript.created_at,
                 transcript.duration_ms as i64,
                 transcript.text,
-                language,
+                transcript.language,
+                language_probs,
+                transcript.translated,
+                filtered,
+                speaker_names,
+                replacements,
+                vocab_suggestions,
                 tags,
                 transcript.title,
                 transcript.summary,
@@ -475,6 +970,8 @@ fn save_transcripts_to_conn(
                 TRANSCRIPT_SOURCE,
             ])
             .map_err(|err| err.to_string())?;
+            write_segments(&tx, transcript)?;
+            write_words(&tx, transcript)?;
         }
     }
 
@@ -527,7 +1024,9 @@ pub fn load_transcripts(settings: &Settings) -> Vec<Transcript> {
     }
 
     let mut stmt = match conn.prepare(
-        "SELECT id, created_at, duration_ms, text, title, summary, tags, embedding, audio_path
+        "SELECT id, created_at, duration_ms, text, title, summary, tags, embedding, audio_path,
+       language, language_probs, translated, filtered, speaker_names, replacements,
+       vocab_suggestions
      FROM transcripts
      ORDER BY created_at DESC",
     ) {
@@ -551,14 +1050,42 @@ pub fn load_transcripts(settings: &Settings) -> Vec<Transcript> {
                 .get::<_, Option<String>>(7)?
                 .and_then(|raw| serde_json::from_str::<Vec<f32>>(&raw).ok()),
             audio_path: row.get::<_, Option<String>>(8)?,
+            segments: Vec::new(),
+            words: Vec::new(),
+            language: row.get::<_, Option<String>>(9)?,
+            language_probabilities: row
+                .get::<_, Option<String>>(10)?
+                .and_then(|raw| serde_json::from_str::<Vec<LanguageProbability>>(&raw).ok())
+                .unwrap_or_default(),
+            translated: row.get::<_, Option<bool>>(11)?.unwrap_or(false),
+            filtered: row
+                .get::<_, Option<String>>(12)?
+                .and_then(|raw| serde_json::from_str::<Vec<FilteredSegment>>(&raw).ok())
+                .unwrap_or_default(),
+            speaker_names: row
+                .get::<_, Option<String>>(13)?
+                .and_then(|raw| serde_json::from_str::<Vec<String>>(&raw).ok())
+                .unwrap_or_default(),
+            replacements: row
+                .get::<_, Option<String>>(14)?
+                .and_then(|raw| serde_json::from_str::<Vec<AppliedReplacement>>(&raw).ok())
+                .unwrap_or_default(),
+            vocab_suggestions: row
+                .get::<_, Option<String>>(15)?
+                .and_then(|raw| serde_json::from_str::<Vec<VocabSuggestion>>(&raw).ok())
+                .unwrap_or_default(),
         })
     }) {
         Ok(rows) => rows,
         Err(_) => return Vec::new(),
     };
 
+    let mut segments = load_segments(&conn).unwrap_or_default();
+    let mut words = load_words(&conn).unwrap_or_default();
     let mut transcripts = Vec::new();
-    for transcript in rows.flatten() {
+    for mut transcript in rows.flatten() {
+        transcript.segments = segments.remove(&transcript.id).unwrap_or_default();
+        transcript.words = words.remove(&transcript.id).unwrap_or_default();
         transcripts.push(transcript);
     }
 
@@ -657,33 +1184,39 @@ pub fn save_transcripts(settings: &Settings, transcripts: &[Transcript]) -> Resu
     let path = db_path(settings);
     let mut conn = open_connection(&path)?;
     ensure_schema(&conn)?;
-    save_transcripts_to_conn(&mut conn, settings, transcripts)
+    save_transcripts_to_conn(&mut conn, transcripts)
 }
 
 pub fn upsert_transcript(settings: &Settings, transcript: &Transcript) -> Result<(), String> {
     let path = db_path(settings);
-    let conn = open_connection(&path)?;
+    let mut conn = open_connection(&path)?;
     ensure_schema(&conn)?;
 
-    let language = if settings.transcription.language.is_empty() {
-        None
-    } else {
-        Some(settings.transcription.language.as_str())
-    };
-
     let tags = encode_tags(&transcript.tags)?;
+    let language_probs = encode_language_probabilities(&transcript.language_probabilities)?;
+    let filtered = encode_filtered(&transcript.filtered)?;
+    let speaker_names = encode_tags(&transcript.speaker_names)?;
+    let replacements = encode_replacements(&transcript.replacements)?;
+    let vocab_suggestions = encode_vocab_suggestions(&transcript.vocab_suggestions)?;
     let embedding = encode_embedding(&transcript.embedding)?;
 
-    conn
+    let tx = conn.transaction().map_err(|err| err.to_string())?;
+    tx
     .execute(
       "INSERT INTO transcripts
-        (id, created_at, duration_ms, text, language, tags, title, summary, embedding, audio_path, source)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+        (id, created_at, duration_ms, text, language, language_probs, translated, filtered, speaker_names, replacements, vocab_suggestions, tags, title, summary, embedding, audio_path, source)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
         ON CONFLICT(id) DO UPDATE SET
           created_at = excluded.created_at,
           duration_ms = excluded.duration_ms,
           text = excluded.text,
           language = excluded.language,
+          language_probs = excluded.language_probs,
+          translated = excluded.translated,
+          filtered = excluded.filtered,
+          speaker_names = excluded.speaker_names,
+          replacements = excluded.replacements,
+          vocab_suggestions = excluded.vocab_suggestions,
           tags = excluded.tags,
           title = excluded.title,
           summary = excluded.summary,
@@ -695,7 +1228,13 @@ pub fn upsert_transcript(settings: &Settings, transcript: &Transcript) -> Result
         transcript.created_at,
         transcript.duration_ms as i64,
         transcript.text,
-        language,
+        transcript.language,
+        language_probs,
+        transcript.translated,
+        filtered,
+        speaker_names,
+        replacements,
+        vocab_suggestions,
         tags,
         transcript.title,
         transcript.summary,
@@ -705,8 +1244,10 @@ pub fn upsert_transcript(settings: &Settings, transcript: &Transcript) -> Result
       ],
     )
     .map_err(|err| err.to_string())?;
+    write_segments(&tx, transcript)?;
+    write_words(&tx, transcript)?;
 
-    Ok(())
+    tx.commit().map_err(|err| err.to_string())
 }
 
 pub fn delete_transcript_row(settings: &Settings, id: &str) -> Result<(), String> {
@@ -715,6 +1256,16 @@ pub fn delete_transcript_row(settings: &Settings, id: &str) -> Result<(), String
     ensure_schema(&conn)?;
     conn.execute("DELETE FROM transcripts WHERE id = ?1", params![id])
         .map_err(|err| err.to_string())?;
+    conn.execute(
+        "DELETE FROM transcript_segments WHERE transcript_id = ?1",
+        params![id],
+    )
+    .map_err(|err| err.to_string())?;
+    conn.execute(
+        "DELETE FROM transcript_words WHERE transcript_id = ?1",
+        params![id],
+    )
+    .map_err(|err| err.to_string())?;
     Ok(())
 }
 
@@ -724,6 +1275,10 @@ pub fn clear_transcripts_table(settings: &Settings) -> Result<(), String> {
     ensure_schema(&conn)?;
     conn.execute("DELETE FROM transcripts", [])
         .map_err(|err| err.to_string())?;
+    conn.execute("DELETE FROM transcript_segments", [])
+        .map_err(|err| err.to_string())?;
+    conn.execute("DELETE FROM transcript_words", [])
+        .map_err(|err| err.to_string())?;
     Ok(())
 }
 
@@ -756,10 +1311,268 @@ pub fn delete_clip(settings: &Settings, id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Dictation commands, optionally only those for `language`.
+pub fn load_dictation_commands(
+    settings: &Settings,
+    language: Option<&str>,
+) -> Result<Vec<DictationCommand>, String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, language, phrase, action, value
+       FROM dictation_commands
+       WHERE ?1 IS NULL OR language = ?1
+       ORDER BY language, phrase",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map(params![language], |row| {
+            Ok(DictationCommand {
+                id: row.get(0)?,
+                language: row.get(1)?,
+                phrase: row.get(2)?,
+                action: row.get(3)?,
+                value: row.get(4)?,
+            })
+        })
+        .map_err(|err| err.to_string())?;
+    Ok(rows.flatten().collect())
+}
+
+pub fn upsert_dictation_command(
+    settings: &Settings,
+    command: &DictationCommand,
+) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    insert_dictation_command(&conn, command)
+}
+
+pub fn delete_dictation_command(settings: &Settings, id: &str) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    conn.execute("DELETE FROM dictation_commands WHERE id = ?1", params![id])
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+pub fn insert_benchmark(settings: &Settings, result: &BenchmarkResult) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    conn.execute(
+        "INSERT INTO benchmarks
+         (id, run_id, created_at, model, threads, gpu, cold_load_ms, audio_seconds, duration_ms,
+          peak_memory_bytes, gpu_name, cpu_count)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        params![
+            result.id,
+            result.run_id,
+            result.created_at,
+            result.model,
+            result.threads,
+            result.gpu,
+            result.cold_load_ms as i64,
+            result.audio_seconds,
+            result.duration_ms as i64,
+            result.peak_memory_bytes.map(|bytes| bytes as i64),
+            result.gpu_name,
+            result.cpu_count,
+        ],
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Benchmark results, newest run first and fastest first within a run.
+pub fn load_benchmarks(settings: &Settings) -> Result<Vec<BenchmarkResult>, String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, run_id, created_at, model, threads, gpu, cold_load_ms, audio_seconds,
+         duration_ms, peak_memory_bytes, gpu_name, cpu_count
+       FROM benchmarks
+       ORDER BY created_at DESC, run_id, audio_seconds * 1000.0 / MAX(duration_ms, 1) DESC",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            let audio_seconds: f32 = row.get(7)?;
+            let duration_ms = row.get::<_, i64>(8)?.max(0) as u64;
+            Ok(BenchmarkResult {
+                id: row.get(0)?,
+                run_id: row.get(1)?,
+                created_at: row.get(2)?,
+                model: row.get(3)?,
+                threads: row.get(4)?,
+                gpu: row.get(5)?,
+                cold_load_ms: row.get::<_, i64>(6)?.max(0) as u64,
+                audio_seconds,
+                duration_ms,
+                realtime_factor: audio_seconds / (duration_ms as f32 / 1000.0).max(0.001),
+                peak_memory_bytes: row
+                    .get::<_, Option<i64>>(9)?
+                    .map(|bytes| bytes.max(0) as u64),
+                gpu_name: row.get(10)?,
+                cpu_count: row.get(11)?,
+            })
+        })
+        .map_err(|err| err.to_string())?;
+    Ok(rows.flatten().collect())
+}
+
+pub fn insert_evaluation(settings: &Settings, result: &EvaluationResult) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    let files = serde_json::to_string(&result.files).map_err(|err| err.to_string())?;
+    conn.execute(
+        "INSERT INTO evaluations
+         (id, run_id, created_at, model, preset, custom_vocab, wer, cer, substitutions, deletions,
+          insertions, reference_words, audio_seconds, duration_ms, files)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        params![
+            result.id,
+            result.run_id,
+            result.created_at,
+            result.model,
+            result.preset,
+            result.custom_vocab,
+            result.wer,
+            result.cer,
+            result.substitutions as i64,
+            result.deletions as i64,
+            result.insertions as i64,
+            result.reference_words as i64,
+            result.audio_seconds,
+            result.duration_ms as i64,
+            files,
+        ],
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Evaluation results, newest run first and best WER first within a run.
+pub fn load_evaluations(settings: &Settings) -> Result<Vec<EvaluationResult>, String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, run_id, created_at, model, preset, custom_vocab, wer, cer, substitutions,
+         deletions, insertions, reference_words, audio_seconds, duration_ms, files
+       FROM evaluations
+       ORDER BY created_at DESC, run_id, wer",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            let audio_seconds: f32 = row.get(12)?;
+            let duration_ms = row.get::<_, i64>(13)?.max(0) as u64;
+            Ok(EvaluationResult {
+                id: row.get(0)?,
+                run_id: row.get(1)?,
+                created_at: row.get(2)?,
+                model: row.get(3)?,
+                preset: row.get(4)?,
+                custom_vocab: row.get(5)?,
+                wer: row.get(6)?,
+                cer: row.get(7)?,
+                substitutions: row.get::<_, i64>(8)?.max(0) as usize,
+                deletions: row.get::<_, i64>(9)?.max(0) as usize,
+                insertions: row.get::<_, i64>(10)?.max(0) as usize,
+                reference_words: row.get::<_, i64>(11)?.max(0) as usize,
+                audio_seconds,
+                duration_ms,
+                realtime_factor: audio_seconds / (duration_ms as f32 / 1000.0).max(0.001),
+                files: serde_json::from_str::<Vec<EvaluationFile>>(&row.get::<_, String>(14)?)
+                    .unwrap_or_default(),
+            })
+        })
+        .map_err(|err| err.to_string())?;
+    Ok(rows.flatten().collect())
+}
+
+/// Replacement rules in the order they were created, which is the order they apply in.
+pub fn load_replacement_rules(settings: &Settings) -> Result<Vec<ReplacementRule>, String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, pattern, replacement, mode, case_sensitive, preserve_case, enabled
+       FROM replacement_rules
+       ORDER BY rowid",
+        )
+        .map_err(|err| err.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(ReplacementRule {
+                id: row.get(0)?,
+                pattern: row.get(1)?,
+                replacement: row.get(2)?,
+                mode: row.get(3)?,
+                case_sensitive: row.get(4)?,
+                preserve_case: row.get(5)?,
+                enabled: row.get(6)?,
+            })
+        })
+        .map_err(|err| err.to_string())?;
+    Ok(rows.flatten().collect())
+}
+
+pub fn upsert_replacement_rule(settings: &Settings, rule: &ReplacementRule) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    conn.execute(
+        "INSERT INTO replacement_rules
+         (id, pattern, replacement, mode, case_sensitive, preserve_case, enabled)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+       ON CONFLICT(id) DO UPDATE SET
+         pattern = excluded.pattern,
+         replacement = excluded.replacement,
+         mode = excluded.mode,
+         case_sensitive = excluded.case_sensitive,
+         preserve_case = excluded.preserve_case,
+         enabled = excluded.enabled",
+        params![
+            rule.id,
+            rule.pattern,
+            rule.replacement,
+            rule.mode,
+            rule.case_sensitive,
+            rule.preserve_case,
+            rule.enabled,
+        ],
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+pub fn delete_replacement_rule(settings: &Settings, id: &str) -> Result<(), String> {
+    let path = db_path(settings);
+    let conn = open_connection(&path)?;
+    ensure_schema(&conn)?;
+    conn.execute("DELETE FROM replacement_rules WHERE id = ?1", params![id])
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
 
     #[test]
     fn expand_tilde_handles_windows_separator() {
@@ -776,6 +1589,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decoding_settings_roundtrip() {
+        let mut settings = Settings::default();
+        settings.transcription.decoding_preset = "custom".to_string();
+        settings.transcription.beam_size = 8;
+        settings.transcription.beam_patience = 1.5;
+        settings.transcription.temperature = 0.1;
+        settings.transcription.temperature_increment = 0.0;
+        settings.transcription.entropy_threshold = 2.8;
+        settings.transcription.logprob_threshold = -0.5;
+        settings.transcription.no_speech_threshold = 0.4;
+
+        let mut loaded = Settings::default();
+        for (key, value) in settings_entries(&settings) {
+            apply_setting(&mut loaded, key, value);
+        }
+
+        let expected = serde_json::to_value(&settings.transcription).expect("encode");
+        let actual = serde_json::to_value(&loaded.transcription).expect("encode");
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn upsert_and_delete_transcript_roundtrip() {
         let mut settings = Settings::default();
@@ -791,6 +1626,39 @@ mod tests {
             summary: None,
             tags: vec!["a".to_string(), "b".to_string()],
             audio_path: None,
+            segments: Vec::new(),
+            words: Vec::new(),
+            language: Some("de".to_string()),
+            language_probabilities: vec![
+                LanguageProbability {
+                    language: "de".to_string(),
+                    probability: 0.9,
+                },
+                LanguageProbability {
+                    language: "en".to_string(),
+                    probability: 0.08,
+                },
+            ],
+            translated: true,
+            filtered: vec![FilteredSegment {
+                start_ms: 1_000,
+                end_ms: 2_000,
+                text: "Thanks for watching!".to_string(),
+                reason: "known_phrase".to_string(),
+            }],
+            speaker_names: vec!["Ana".to_string(), "Ben".to_string()],
+            replacements: vec![AppliedReplacement {
+                rule_id: "rule".to_string(),
+                pattern: "cube control".to_string(),
+                replacement: "kubectl".to_string(),
+                count: 2,
+            }],
+            vocab_suggestions: vec![VocabSuggestion {
+                original: "cooper netties".to_string(),
+                suggestion: "Kubernetes".to_string(),
+                score: 0.8,
+                applied: false,
+            }],
             embedding: Some(vec![0.1, 0.2, 0.3]),
         };
 
@@ -800,12 +1668,237 @@ mod tests {
         assert_eq!(loaded[0].id, transcript.id);
         assert_eq!(loaded[0].text, transcript.text);
         assert_eq!(loaded[0].tags, transcript.tags);
+        assert_eq!(loaded[0].language.as_deref(), Some("de"));
+        assert!(loaded[0].translated);
+        assert_eq!(loaded[0].filtered, transcript.filtered);
+        assert_eq!(loaded[0].speaker_names, transcript.speaker_names);
+        assert_eq!(loaded[0].replacements, transcript.replacements);
+        assert_eq!(loaded[0].vocab_suggestions, transcript.vocab_suggestions);
+        assert_eq!(
+            loaded[0].language_probabilities,
+            transcript.language_probabilities
+        );
         assert!(loaded[0].embedding.is_some());
 
+        // A full re-save keeps each row's own language instead of the current setting.
+        save_transcripts(&settings, &loaded).expect("save");
+        let loaded = load_transcripts(&settings);
+        assert_eq!(loaded[0].language.as_deref(), Some("de"));
+
         delete_transcript_row(&settings, &transcript.id).expect("delete");
         let loaded = load_transcripts(&settings);
         assert!(loaded.is_empty());
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn transcript_segments_roundtrip() {
+        let mut settings = Settings::default();
+        let dir = std::env::temp_dir().join(format!("whispr-test-{}", Uuid::new_v4()));
+        settings.storage.data_dir = dir.to_string_lossy().to_string();
+
+        let segments = vec![
+            TranscriptSegment {
+                start_ms: 0,
+                end_ms: 1_200,
+                text: "hello".to_string(),
+                speaker: None,
+            },
+            TranscriptSegment {
+                start_ms: 1_200,
+                end_ms: 2_500,
+                text: "world".to_string(),
+                speaker: Some(1),
+            },
+        ];
+        let mut transcript = Transcript {
+            id: Uuid::new_v4().to_string(),
+            created_at: 123,
+            duration_ms: 2_500,
+            text: "hello world".to_string(),
+            title: None,
+            summary: None,
+            tags: Vec::new(),
+            audio_path: None,
+            segments: segments.clone(),
+            words: vec![
+                TranscriptWord {
+                    start_ms: 0,
+                    end_ms: 1_200,
+                    text: "hello".to_string(),
+                    confidence: 0.5,
+                },
+                TranscriptWord {
+                    start_ms: 1_200,
+                    end_ms: 2_500,
+                    text: "world".to_string(),
+                    confidence: 0.25,
+                },
+            ],
+            language: None,
+            language_probabilities: Vec::new(),
+            translated: false,
+            filtered: Vec::new(),
+            speaker_names: Vec::new(),
+            replacements: Vec::new(),
+            vocab_suggestions: Vec::new(),
+            embedding: None,
+        };
+
+        upsert_transcript(&settings, &transcript).expect("upsert");
+        let loaded = load_transcripts(&settings);
+        assert_eq!(loaded[0].segments, segments);
+        assert_eq!(loaded[0].words, transcript.words);
+
+        // Re-saving replaces the previous segment rows instead of appending to them.
+        transcript.segments.truncate(1);
+        upsert_transcript(&settings, &transcript).expect("upsert");
+        let loaded = load_transcripts(&settings);
+        assert_eq!(loaded[0].segments, segments[..1]);
+
+        delete_transcript_row(&settings, &transcript.id).expect("delete");
+        let conn = open_connection(&db_path(&settings)).expect("open");
+        assert!(load_segments(&conn).expect("segments").is_empty());
+        assert!(load_words(&conn).expect("words").is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn dictation_commands_are_seeded_once_and_editable() {
+        let mut settings = Settings::default();
+        let dir = std::env::temp_dir().join(format!("whispr-test-{}", Uuid::new_v4()));
+        settings.storage.data_dir = dir.to_string_lossy().to_string();
+
+        let seeded = load_dictation_commands(&settings, Some("en")).expect("load");
+        assert_eq!(seeded.len(), dictation::default_commands().len());
+        assert!(load_dictation_commands(&settings, Some("de"))
+            .expect("load")
+            .is_empty());
+
+        let mut command = seeded
+            .iter()
+            .find(|command| command.phrase == "comma")
+            .cloned()
+            .expect("comma");
+        command.phrase = "komma".to_string();
+        command.language = "de".to_string();
+        upsert_dictation_command(&settings, &command).expect("upsert");
+        delete_dictation_command(&settings, &seeded[0].id).expect("delete");
+
+        // Deleting defaults doesn't bring them back on the next open.
+        let all = load_dictation_commands(&settings, None).expect("load");
+        assert_eq!(all.len(), seeded.len() - 1);
+        assert_eq!(
+            load_dictation_commands(&settings, Some("de")).expect("load"),
+            vec![command]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn replacement_rules_keep_their_order() {
+        let mut settings = Settings::default();
+        let dir = std::env::temp_dir().join(format!("whispr-test-{}", Uuid::new_v4()));
+        settings.storage.data_dir = dir.to_string_lossy().to_string();
+
+        let rule = |id: &str, pattern: &str| ReplacementRule {
+            id: id.to_string(),
+            pattern: pattern.to_string(),
+            replacement: "x".to_string(),
+            mode: "whole_word".to_string(),
+            case_sensitive: false,
+            preserve_case: true,
+            enabled: true,
+        };
+        upsert_replacement_rule(&settings, &rule("b", "first")).expect("upsert");
+        upsert_replacement_rule(&settings, &rule("a", "second")).expect("upsert");
+        let mut edited = rule("b", "first, edited");
+        edited.enabled = false;
+        upsert_replacement_rule(&settings, &edited).expect("upsert");
+
+        let rules = load_replacement_rules(&settings).expect("load");
+        assert_eq!(rules, vec![edited, rule("a", "second")]);
+
+        delete_replacement_rule(&settings, "b").expect("delete");
+        assert_eq!(load_replacement_rules(&settings).expect("load").len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn evaluations_round_trip_newest_run_first() {
+        let mut settings = Settings::default();
+        let dir = std::env::temp_dir().join(format!("whispr-test-{}", Uuid::new_v4()));
+        settings.storage.data_dir = dir.to_string_lossy().to_string();
+
+        let result = |run_id: &str, created_at: i64, model: &str, wer: f32| EvaluationResult {
+            id: Uuid::new_v4().to_string(),
+            run_id: run_id.to_string(),
+            created_at,
+            model: model.to_string(),
+            preset: "fast".to_string(),
+            custom_vocab: "PostgREST".to_string(),
+            wer,
+            cer: wer / 2.0,
+            substitutions: 3,
+            deletions: 1,
+            insertions: 0,
+            reference_words: 40,
+            audio_seconds: 12.0,
+            duration_ms: 3_000,
+            realtime_factor: 4.0,
+            files: vec![EvaluationFile {
+                name: "a.wav".to_string(),
+                wer,
+                cer: wer / 2.0,
+                hypothesis: "hello".to_string(),
+            }],
+        };
+        let old = result("old", 1_000, "tiny.en", 0.2);
+        let worse = result("new", 2_000, "tiny.en", 0.3);
+        let better = result("new", 2_000, "small.en", 0.1);
+        for item in [&old, &worse, &better] {
+            insert_evaluation(&settings, item).expect("insert");
+        }
+
+        let loaded = load_evaluations(&settings).expect("load");
+        assert_eq!(loaded, vec![better, worse, old]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn benchmarks_round_trip_fastest_first() {
+        let mut settings = Settings::default();
+        let dir = std::env::temp_dir().join(format!("whispr-test-{}", Uuid::new_v4()));
+        settings.storage.data_dir = dir.to_string_lossy().to_string();
+
+        let result = |threads: u32, duration_ms: u64, peak_memory_bytes| BenchmarkResult {
+            id: Uuid::new_v4().to_string(),
+            run_id: "run".to_string(),
+            created_at: 1_000,
+            model: "base.en".to_string(),
+            threads,
+            gpu: false,
+            cold_load_ms: 250,
+            audio_seconds: 10.0,
+            duration_ms,
+            realtime_factor: 10_000.0 / duration_ms as f32,
+            peak_memory_bytes,
+            gpu_name: Some("Test GPU".to_string()),
+            cpu_count: 8,
+        };
+        let slow = result(1, 4_000, None);
+        let fast = result(8, 1_000, Some(300 * 1024 * 1024));
+        insert_benchmark(&settings, &slow).expect("insert");
+        insert_benchmark(&settings, &fast).expect("insert");
+
+        let loaded = load_benchmarks(&settings).expect("load");
+        assert_eq!(loaded, vec![fast, slow]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,462 @@
+use crate::core::audio::RecordedAudio;
+use crate::core::transcription::{self, TranscriptionOutput};
+use crate::settings::Settings;
+use crate::types::{FilteredSegment, TranscriptSegment, TranscriptWord};
+
+/// Audio re-decoded before the commit point so a word cut at a loose timestamp isn't clipped.
+pub const WINDOW_OVERLAP_MS: i64 = 250;
+/// Longest window decoded per tick, kept under whisper's 30 s input. Past this the current
+/// hypothesis is committed as-is so the window and the commit lag stay bounded.
+pub const MAX_WINDOW_MS: i64 = 20_000;
+/// Leftover audio after a reused prefix shorter than this isn't worth a final pass.
+const MIN_REMAINDER_MS: i64 = 300;
+
+/// Words from the live preview that no later decode may change, on the recording's timeline.
+#[derive(Debug, Clone, Default)]
+pub struct StreamPrefix {
+    /// One segment per commit.
+    pub segments: Vec<TranscriptSegment>,
+    pub words: Vec<TranscriptWord>,
+    /// Everything before this point is covered by `words`.
+    pub end_ms: i64,
+    pub language: Option<String>,
+    /// Hallucinations the preview dropped before `end_ms`.
+    pub filtered: Vec<FilteredSegment>,
+}
+
+/// Streaming preview state using local agreement: each tick decodes the audio from the commit
+/// point to "now", and the leading words two consecutive decodes agree on are committed. The
+/// rest of the latest decode is tentative and may still change.
+#[derive(Debug)]
+pub struct StreamingTranscript {
+    committed: StreamPrefix,
+    tentative: Vec<TranscriptWord>,
+    /// Cleared when audio is skipped, after which `committed` no longer covers the recording
+    /// from its start and can't stand in for the final transcript.
+    contiguous: bool,
+}
+
+impl Default for StreamingTranscript {
+    fn default() -> Self {
+        Self {
+            committed: StreamPrefix::default(),
+            tentative: Vec::new(),
+            contiguous: true,
+        }
+    }
+}
+
+impl StreamingTranscript {
+    /// Where the next decode window should begin.
+    pub fn window_start_ms(&self) -> i64 {
+        (self.committed.end_ms - WINDOW_OVERLAP_MS).max(0)
+    }
+
+    /// Fold in a decode of the window `[window_start_ms, window_end_ms)`. Word timestamps in
+    /// `output` are relative to the window start.
+    pub fn update(
+        &mut self,
+        window_start_ms: i64,
+        window_end_ms: i64,
+        output: TranscriptionOutput,
+    ) {
+        if output.language.is_some() {
+            self.committed.language = output.language;
+        }
+
+        let committed_end_ms = self.committed.end_ms;
+        let words: Vec<TranscriptWord> = output
+            .words
+            .into_iter()
+            .map(|word| TranscriptWord {
+                start_ms: word.start_ms + window_start_ms,
+                end_ms: word.end_ms + window_start_ms,
+                ..word
+            })
+            // Words from the overlap were already committed on an earlier tick.
+            .filter(|word| (word.start_ms + word.end_ms) / 2 >= committed_end_ms)
+            .collect();
+
+        let forced = window_end_ms - window_start_ms >= MAX_WINDOW_MS;
+        let commit = if forced {
+            words.len()
+        } else {
+            agreed_prefix_len(&self.tentative, &words)
+        };
+
+        if commit > 0 {
+            let batch = &words[..commit];
+            let text = batch
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let end_ms = batch[commit - 1].end_ms.max(committed_end_ms);
+            self.committed.segments.push(TranscriptSegment {
+                start_ms: batch[0].start_ms,
+                end_ms,
+                text,
+                speaker: None,
+            });
+            self.committed.words.extend_from_slice(batch);
+            self.committed.end_ms = end_ms;
+        } else if forced && words.is_empty() {
+            // A long stretch with no speech: move the window on instead of re-decoding silence.
+            self.committed.end_ms = (window_end_ms - WINDOW_OVERLAP_MS).max(committed_end_ms);
+        }
+        let end_ms = self.committed.end_ms;
+        self.committed.filtered.extend(
+            output
+                .filtered
+                .into_iter()
+                .map(|item| FilteredSegment {
+                    start_ms: item.start_ms + window_start_ms,
+                    end_ms: item.end_ms + window_start_ms,
+                    ..item
+                })
+                .filter(|item| {
+                    let midpoint = (item.start_ms + item.end_ms) / 2;
+                    midpoint >= committed_end_ms && midpoint < end_ms
+                }),
+        );
+        self.tentative = words[commit..].to_vec();
+    }
+
+    /// Jump the window forward to `position_ms`, abandoning any audio before it.
+    pub fn skip_to(&mut self, position_ms: i64) {
+        if position_ms <= self.committed.end_ms {
+            return;
+        }
+        self.committed.end_ms = position_ms;
+        self.tentative.clear();
+        self.contiguous = false;
+    }
+
+    pub fn committed_text(&self) -> String {
+        join_segments(&self.committed.segments)
+    }
+
+    pub fn tentative_text(&self) -> String {
+        self.tentative
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The committed words, if they cover the recording from its first sample.
+    pub fn prefix(&self) -> Option<StreamPrefix> {
+        if !self.contiguous || self.committed.words.is_empty() {
+            return None;
+        }
+        Some(self.committed.clone())
+    }
+}
+
+/// Final transcription that reuses the preview's committed prefix and only decodes the audio
+/// after it. The prefix keeps the preview's (greedy) decoding; the remainder uses the full pass.
+pub fn transcribe_remainder(
+    settings: &Settings,
+    audio: RecordedAudio,
+    prefix: StreamPrefix,
+    previous: &str,
+) -> Result<TranscriptionOutput, String> {
+    let channels = audio.channels.max(1) as usize;
+    let frames = audio.samples.len() / channels;
+    let start_ms = (prefix.end_ms - WINDOW_OVERLAP_MS).max(0);
+    let start_frame = ((start_ms * audio.sample_rate as i64 / 1000) as usize).min(frames);
+    let remaining_ms = ((frames - start_frame) as i64 * 1000) / audio.sample_rate.max(1) as i64;
+
+    let remainder = if remaining_ms - WINDOW_OVERLAP_MS >= MIN_REMAINDER_MS {
+        let rest = RecordedAudio {
+            samples: audio.samples[start_frame * channels..].to_vec(),
+            sample_rate: audio.sample_rate,
+            channels: audio.channels,
+        };
+        Some(transcription::transcribe(settings, rest, previous)?)
+    } else {
+        None
+    };
+
+    Ok(merge_remainder(
+        prefix,
+        remainder,
+        start_ms,
+        settings.transcription.translate,
+    ))
+}
+
+/// Append the remainder decode (timestamps relative to `offset_ms`) after the prefix, dropping
+/// what it re-heard from the overlap.
+fn merge_remainder(
+    prefix: StreamPrefix,
+    remainder: Option<TranscriptionOutput>,
+    offset_ms: i64,
+    translated: bool,
+) -> TranscriptionOutput {
+    let StreamPrefix {
+        mut segments,
+        mut words,
+        end_ms,
+        mut language,
+        mut filtered,
+    } = prefix;
+    let mut language_probabilities = Vec::new();
+    let after_prefix = |start_ms: i64, end: i64| offset_ms + (start_ms + end) / 2 >= end_ms;
+
+    if let Some(output) = remainder {
+        for segment in output.segments {
+            let (new, heard): (Vec<&TranscriptWord>, Vec<&TranscriptWord>) = output
+                .words
+                .iter()
+                .filter(|word| {
+                    let middle = (word.start_ms + word.end_ms) / 2;
+                    (segment.start_ms..=segment.end_ms).contains(&middle)
+                })
+                .partition(|word| after_prefix(word.start_ms, word.end_ms));
+            // A segment can open with a word the prefix already has; keep only what follows it.
+            let (start_ms, text) = if heard.is_empty() && new.is_empty() {
+                if !after_prefix(segment.start_ms, segment.end_ms) {
+                    continue;
+                }
+                (segment.start_ms, segment.text)
+            } else if heard.is_empty() {
+                (segment.start_ms, segment.text)
+            } else if let Some(first) = new.first() {
+                let text = new
+                    .iter()
+                    .map(|word| word.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (first.start_ms, text)
+            } else {
+                continue;
+            };
+            segments.push(TranscriptSegment {
+                start_ms: start_ms + offset_ms,
+                end_ms: segment.end_ms + offset_ms,
+                text,
+                speaker: segment.speaker,
+            });
+        }
+        words.extend(
+            output
+                .words
+                .into_iter()
+                .filter(|word| after_prefix(word.start_ms, word.end_ms))
+                .map(|word| TranscriptWord {
+                    start_ms: word.start_ms + offset_ms,
+                    end_ms: word.end_ms + offset_ms,
+                    ..word
+                }),
+        );
+        filtered.extend(
+            output
+                .filtered
+                .into_iter()
+                .filter(|item| after_prefix(item.start_ms, item.end_ms))
+                .map(|item| FilteredSegment {
+                    start_ms: item.start_ms + offset_ms,
+                    end_ms: item.end_ms + offset_ms,
+                    ..item
+                }),
+        );
+        language = output.language.or(language);
+        language_probabilities = output.language_probabilities;
+    }
+
+    TranscriptionOutput {
+        text: join_segments(&segments),
+        segments,
+        words,
+        language,
+        language_probabilities,
+        translated,
+        filtered,
+    }
+}
+
+/// Number of leading words two hypotheses share, ignoring case and punctuation.
+fn agreed_prefix_len(previous: &[TranscriptWord], current: &[TranscriptWord]) -> usize {
+    previous
+        .iter()
+        .zip(current)
+        .take_while(|(a, b)| normalize_word(&a.text) == normalize_word(&b.text))
+        .count()
+}
+
+fn normalize_word(text: &str) -> String {
+    text.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn join_segments(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start_ms: i64, end_ms: i64) -> TranscriptWord {
+        TranscriptWord {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            confidence: 0.9,
+        }
+    }
+
+    fn output(words: Vec<TranscriptWord>) -> TranscriptionOutput {
+        let text = words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let segments = match (words.first(), words.last()) {
+            (Some(first), Some(last)) => vec![TranscriptSegment {
+                start_ms: first.start_ms,
+                end_ms: last.end_ms,
+                text: text.clone(),
+                speaker: None,
+            }],
+            _ => Vec::new(),
+        };
+        TranscriptionOutput {
+            text,
+            segments,
+            words,
+            language: Some("en".to_string()),
+            language_probabilities: Vec::new(),
+            translated: false,
+            filtered: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn commits_words_two_decodes_agree_on() {
+        let mut stream = StreamingTranscript::default();
+        stream.update(
+            0,
+            2_000,
+            output(vec![word("Hello", 0, 400), word("there", 400, 900)]),
+        );
+        assert_eq!(stream.committed_text(), "");
+        assert_eq!(stream.tentative_text(), "Hello there");
+
+        stream.update(
+            0,
+            4_000,
+            output(vec![
+                word("hello,", 0, 400),
+                word("their", 400, 900),
+                word("friend", 900, 1_400),
+            ]),
+        );
+        assert_eq!(stream.committed_text(), "hello,");
+        assert_eq!(stream.tentative_text(), "their friend");
+        assert_eq!(stream.window_start_ms(), 400 - WINDOW_OVERLAP_MS);
+    }
+
+    #[test]
+    fn overlap_words_are_not_committed_twice() {
+        let mut stream = StreamingTranscript::default();
+        stream.update(
+            0,
+            2_000,
+            output(vec![word("one", 0, 500), word("two", 600, 1_000)]),
+        );
+        stream.update(
+            0,
+            3_000,
+            output(vec![word("one", 0, 500), word("two", 600, 1_000)]),
+        );
+        assert_eq!(stream.committed_text(), "one two");
+
+        // The next window starts inside "two" and hears it again.
+        let start = stream.window_start_ms();
+        let next = || output(vec![word("two", 0, 250), word("three", 300, 800)]);
+        stream.update(start, 4_000, next());
+        stream.update(start, 5_000, next());
+        assert_eq!(stream.committed_text(), "one two three");
+        assert_eq!(stream.committed.words.len(), 3);
+        assert_eq!(stream.committed.end_ms, start + 800);
+    }
+
+    #[test]
+    fn long_window_forces_commit() {
+        let mut stream = StreamingTranscript::default();
+        stream.update(0, MAX_WINDOW_MS, output(vec![word("drifting", 0, 600)]));
+        assert_eq!(stream.committed_text(), "drifting");
+        assert_eq!(stream.tentative_text(), "");
+
+        // Silence: the window still moves on.
+        let start = stream.window_start_ms();
+        stream.update(start, start + MAX_WINDOW_MS, output(Vec::new()));
+        assert_eq!(
+            stream.window_start_ms(),
+            start + MAX_WINDOW_MS - 2 * WINDOW_OVERLAP_MS
+        );
+    }
+
+    #[test]
+    fn skipped_audio_disables_prefix_reuse() {
+        let mut stream = StreamingTranscript::default();
+        stream.update(0, 2_000, output(vec![word("a", 0, 300)]));
+        stream.update(0, 3_000, output(vec![word("a", 0, 300)]));
+        assert!(stream.prefix().is_some());
+
+        stream.skip_to(20_000);
+        assert!(stream.prefix().is_none());
+        assert_eq!(stream.window_start_ms(), 20_000 - WINDOW_OVERLAP_MS);
+    }
+
+    #[test]
+    fn merge_remainder_appends_after_prefix() {
+        let prefix = StreamPrefix {
+            segments: vec![TranscriptSegment {
+                start_ms: 0,
+                end_ms: 1_000,
+                text: "first part".to_string(),
+                speaker: None,
+            }],
+            words: vec![word("first", 0, 500), word("part", 500, 1_000)],
+            end_ms: 1_000,
+            language: Some("en".to_string()),
+            filtered: Vec::new(),
+        };
+        // Decoded from 750 ms: "part" is heard again in the overlap, in the same segment as
+        // the words after it.
+        let rest = output(vec![
+            word("part", 0, 250),
+            word("two", 300, 700),
+            word("three", 700, 1_500),
+            word("four", 1_500, 3_000),
+        ]);
+
+        let merged = merge_remainder(prefix, Some(rest), 750, false);
+        assert_eq!(merged.text, "first part two three four");
+        let words: Vec<_> = merged
+            .words
+            .iter()
+            .map(|word| (word.text.as_str(), word.start_ms))
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("first", 0),
+                ("part", 500),
+                ("two", 1_050),
+                ("three", 1_450),
+                ("four", 2_250)
+            ]
+        );
+        assert_eq!(merged.segments[1].start_ms, 1_050);
+    }
+}
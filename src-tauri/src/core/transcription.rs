@@ -1,18 +1,38 @@
-#[cfg(target_os = "linux")]
+use std::ffi::c_void;
 use std::fs;
+use std::ops::Range;
 #[cfg(target_os = "macos")]
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
 
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 use crate::core::audio::RecordedAudio;
-use crate::core::models;
-use crate::settings::Settings;
-
-const TARGET_SAMPLE_RATE: u32 = 16_000;
-const PREVIEW_MAX_SECONDS: f32 = 10.0;
+use crate::core::hallucination::{self, Candidate};
+use crate::core::{continuation, http_backend, models, resample, speakers};
+use crate::settings::{Settings, TRANSCRIPTION_BACKEND_HTTP};
+use crate::types::{
+    ConfidenceSpan, FilteredSegment, LanguageProbability, ResidentModel, TranscriptSegment,
+    TranscriptWord,
+};
+
+/// Sample rate every backend receives: whisper's native input rate.
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
 const GPU_FALLBACK_PREFIX: &str = "GPU init failed, falling back to CPU: ";
+pub const DEFAULT_LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+/// `TranscriptionSettings.language` value that asks whisper to detect the spoken language.
+pub const AUTO_LANGUAGE: &str = "auto";
+const LANGUAGE_PROBABILITY_LIMIT: usize = 5;
+/// Long audio is transcribed in pieces of roughly this length so progress can be reported and
+/// cancellation checked between them.
+const CHUNK_TARGET_SECONDS: usize = 60;
+/// How far either side of the target a chunk boundary may move to land on silence.
+const CHUNK_SEARCH_SECONDS: usize = 10;
+/// Audio shared by neighbouring chunks so words at a boundary are heard with context.
+const CHUNK_OVERLAP_MS: usize = 1000;
+const SILENCE_FRAME_MS: usize = 100;
+pub const TRANSCRIPTION_CANCELLED: &str = "Transcription cancelled";
 
 static LAST_GPU_ERROR: OnceLock<Mutex<Option<String>>> = OnceLock::new();
 static GPU_NAME: OnceLock<Option<String>> = OnceLock::new();
@@ -191,45 +211,280 @@ struct ContextKey {
     wants_gpu: bool,
 }
 
-struct CachedContext {
+struct CachedContext<T = WhisperContext> {
     key: ContextKey,
-    ctx: WhisperContext,
+    /// Model id the context was loaded for, for diagnostics.
+    model: String,
+    ctx: T,
     used_gpu: bool,
+    /// Model file size, used as the estimate of the context's resident memory.
+    size_bytes: u64,
+}
+
+/// Loaded contexts, least recently used first. Switching between models that fit in the memory
+/// budget reuses their contexts instead of reloading from disk.
+struct ContextCache<T = WhisperContext> {
+    entries: Vec<CachedContext<T>>,
+}
+
+impl<T> ContextCache<T> {
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Mark `key` as most recently used. Returns false when it isn't cached.
+    fn touch(&mut self, key: &ContextKey) -> bool {
+        let Some(index) = self.entries.iter().position(|entry| &entry.key == key) else {
+            return false;
+        };
+        let entry = self.entries.remove(index);
+        self.entries.push(entry);
+        true
+    }
+
+    /// Drop least recently used contexts until `incoming` more bytes fit in `budget_bytes`.
+    /// The `keep` most recently used contexts stay even if they alone exceed the budget.
+    fn evict(&mut self, incoming: u64, budget_bytes: u64, keep: usize) {
+        while self.entries.len() > keep
+            && self.resident_bytes().saturating_add(incoming) > budget_bytes
+        {
+            self.entries.remove(0);
+        }
+    }
+
+    fn resident_bytes(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size_bytes).sum()
+    }
+
+    fn most_recent(&self) -> Option<&CachedContext<T>> {
+        self.entries.last()
+    }
+}
+
+static CONTEXT_CACHE: Mutex<ContextCache> = Mutex::new(ContextCache::new());
+
+/// Result of a full transcription pass: the joined text plus the timed segments it was built from.
+#[derive(Debug, Clone)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    pub segments: Vec<TranscriptSegment>,
+    pub words: Vec<TranscriptWord>,
+    /// Language whisper actually decoded with (detected when the setting is "auto").
+    pub language: Option<String>,
+    /// Most likely languages from auto-detection; empty when the language was fixed.
+    pub language_probabilities: Vec<LanguageProbability>,
+    pub translated: bool,
+    /// Segments dropped by the hallucination filter.
+    pub filtered: Vec<FilteredSegment>,
 }
 
-static CONTEXT_CACHE: OnceLock<Mutex<Option<CachedContext>>> = OnceLock::new();
+impl TranscriptionOutput {
+    /// Language of `text`: English when translated, otherwise the decoded language, falling back
+    /// to the configured one.
+    pub fn text_language(&self, settings: &Settings) -> Option<String> {
+        if self.translated {
+            return Some("en".to_string());
+        }
+        let configured = settings.transcription.language.trim();
+        self.language
+            .clone()
+            .or_else(|| {
+                (!configured.is_empty() && !configured.eq_ignore_ascii_case(AUTO_LANGUAGE))
+                    .then(|| configured.to_string())
+            })
+            .map(|language| language.to_ascii_lowercase())
+    }
+}
 
-pub fn transcribe(settings: &Settings, audio: RecordedAudio) -> Result<String, String> {
-    with_cached_context(settings, |ctx| {
-        transcribe_with_context(ctx, settings, audio, None)
-    })
+/// How much work a transcription pass should do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    /// Final transcript: configured decoding strategy and language probabilities.
+    Full,
+    /// Live preview: single thread, greedy decoding, and whisper's internal language pick.
+    Preview,
 }
 
-/// Ensure the global Whisper context cache is initialized for the current settings.
+/// A slice of long audio, in samples at `TARGET_SAMPLE_RATE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AudioChunk {
+    /// Samples handed to whisper, including overlap with the neighbouring chunks.
+    decode: Range<usize>,
+    /// Samples whose segments this chunk contributes to the stitched transcript.
+    keep: Range<usize>,
+}
+
+/// A single decoded (non-special) token with its timing and probability.
+struct TokenPiece {
+    bytes: Vec<u8>,
+    start_ms: i64,
+    end_ms: i64,
+    probability: f32,
+}
+
+/// A speech-to-text engine. Audio arrives as mono `TARGET_SAMPLE_RATE` samples and timestamps in
+/// the output are relative to the first sample.
+pub trait TranscriptionBackend: Sync {
+    /// Final transcription, prompted with the custom vocab and `previous` (the end of the
+    /// dictation this one continues, or empty). Backends that can interrupt a running decode stop
+    /// once `cancel` is set and return `TRANSCRIPTION_CANCELLED`.
+    fn transcribe(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+        previous: &str,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<TranscriptionOutput, String>;
+
+    /// Cheaper decode for the live preview; may trade accuracy for latency.
+    fn transcribe_preview(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+    ) -> Result<TranscriptionOutput, String>;
+
+    /// Do slow one-time setup (such as loading the model) ahead of the first transcription.
+    fn warm_up(&self, settings: &Settings) -> Result<(), String>;
+}
+
+/// Local whisper.cpp through `whisper-rs`, sharing one cached context between all passes.
+pub struct WhisperBackend;
+
+impl TranscriptionBackend for WhisperBackend {
+    fn transcribe(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+        previous: &str,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<TranscriptionOutput, String> {
+        with_cached_context(settings, |ctx| {
+            transcribe_samples(ctx, settings, samples, Pass::Full, previous, cancel)
+        })
+    }
+
+    fn transcribe_preview(
+        &self,
+        settings: &Settings,
+        samples: &[f32],
+    ) -> Result<TranscriptionOutput, String> {
+        with_cached_context(settings, |ctx| {
+            transcribe_samples(ctx, settings, samples, Pass::Preview, "", None)
+        })
+    }
+
+    fn warm_up(&self, settings: &Settings) -> Result<(), String> {
+        with_cached_context(settings, |_ctx| Ok(()))
+    }
+}
+
+/// The backend selected by `TranscriptionSettings.backend`.
+pub fn backend(settings: &Settings) -> &'static dyn TranscriptionBackend {
+    match settings.transcription.backend.as_str() {
+        TRANSCRIPTION_BACKEND_HTTP => &http_backend::HttpBackend,
+        _ => &WhisperBackend,
+    }
+}
+
+pub fn transcribe(
+    settings: &Settings,
+    audio: RecordedAudio,
+    previous: &str,
+) -> Result<TranscriptionOutput, String> {
+    let mono = prepare_samples(audio)?;
+    let mut output = backend(settings).transcribe(settings, &mono, previous, None)?;
+    if settings.transcription.detect_speakers {
+        speakers::assign_speakers(&mut output.segments, &mono, TARGET_SAMPLE_RATE);
+    }
+    Ok(output)
+}
+
+/// Transcribe long audio in silence-aligned chunks, reporting percent complete after each one.
+///
+/// `cancel` is checked between chunks and passed to the backend; once set, this returns
+/// `TRANSCRIPTION_CANCELLED`. The language detected in the first chunk is kept for the rest so
+/// a stretch of music or silence can't switch it mid-file.
+pub fn transcribe_chunked<F>(
+    settings: &Settings,
+    audio: RecordedAudio,
+    previous: &str,
+    cancel: &AtomicBool,
+    mut on_progress: F,
+) -> Result<TranscriptionOutput, String>
+where
+    F: FnMut(f32),
+{
+    let backend = backend(settings);
+    let mono = prepare_samples(audio)?;
+    let chunks = plan_chunks(&mono, TARGET_SAMPLE_RATE as usize);
+    let mut settings = settings.clone();
+    let detect_language = settings
+        .transcription
+        .language
+        .trim()
+        .eq_ignore_ascii_case(AUTO_LANGUAGE);
+    let mut combined = TranscriptionOutput {
+        text: String::new(),
+        segments: Vec::new(),
+        words: Vec::new(),
+        language: None,
+        language_probabilities: Vec::new(),
+        translated: settings.transcription.translate,
+        filtered: Vec::new(),
+    };
+
+    on_progress(0.0);
+    for (index, chunk) in chunks.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(TRANSCRIPTION_CANCELLED.to_string());
+        }
+        let output = backend.transcribe(
+            &settings,
+            &mono[chunk.decode.clone()],
+            previous,
+            Some(cancel),
+        )?;
+        if index == 0 {
+            combined.language = output.language.clone();
+            combined.language_probabilities = output.language_probabilities.clone();
+            if detect_language {
+                if let Some(language) = &output.language {
+                    settings.transcription.language = language.clone();
+                }
+            }
+        }
+        stitch_chunk(&mut combined, output, chunk, index + 1 == chunks.len());
+        on_progress(chunk.keep.end as f32 * 100.0 / mono.len() as f32);
+    }
+
+    if settings.transcription.detect_speakers {
+        speakers::assign_speakers(&mut combined.segments, &mono, TARGET_SAMPLE_RATE);
+    }
+    Ok(combined)
+}
+
+/// Warm up the configured backend; for whisper this initializes the global context cache.
 ///
 /// On GPU builds (Metal/CUDA/etc), the first context initialization can be noticeably slow due to
 /// backend setup and model loading. Calling this in a background thread (for example, when
 /// recording starts) shifts that cost away from the "stop recording -> transcribe" hot path.
 pub fn ensure_context(settings: &Settings) -> Result<(), String> {
-    with_cached_context(settings, |_ctx| Ok(()))
+    backend(settings).warm_up(settings)
 }
 
-/// Best-effort preview transcription using the shared cached context.
+/// Best-effort preview transcription on the configured backend.
 ///
-/// This avoids loading the model a second time for preview vs. final transcription.
-pub fn transcribe_preview(settings: &Settings, audio: RecordedAudio) -> Result<String, String> {
-    with_cached_context(settings, |ctx| {
-        transcribe_preview_with_context(ctx, settings, audio)
-    })
-}
-
-pub fn transcribe_preview_with_context(
-    ctx: &WhisperContext,
+/// The whisper backend shares its cached context with the final pass, so the model is loaded only
+/// once. Callers bound the window themselves (see `streaming::MAX_WINDOW_MS`) since they need word
+/// timestamps relative to the audio they passed in.
+pub fn transcribe_preview(
     settings: &Settings,
     audio: RecordedAudio,
-) -> Result<String, String> {
-    let audio = trim_audio(audio, PREVIEW_MAX_SECONDS);
-    transcribe_with_context(ctx, settings, audio, Some(1))
+) -> Result<TranscriptionOutput, String> {
+    let mono = prepare_samples(audio)?;
+    backend(settings).transcribe_preview(settings, &mono)
 }
 
 pub fn last_gpu_error() -> Option<String> {
@@ -249,12 +504,28 @@ pub fn clear_last_gpu_error() {
 }
 
 pub fn invalidate_context_cache() {
-    let cache = CONTEXT_CACHE.get_or_init(|| Mutex::new(None));
-    if let Ok(mut guard) = cache.lock() {
-        *guard = None;
+    if let Ok(mut guard) = CONTEXT_CACHE.lock() {
+        guard.entries.clear();
     }
 }
 
+/// Models with a loaded context, most recently used first.
+pub fn resident_models() -> Vec<ResidentModel> {
+    let Ok(guard) = CONTEXT_CACHE.lock() else {
+        return Vec::new();
+    };
+    guard
+        .entries
+        .iter()
+        .rev()
+        .map(|entry| ResidentModel {
+            model: entry.model.clone(),
+            size_bytes: entry.size_bytes,
+            gpu: entry.used_gpu,
+        })
+        .collect()
+}
+
 fn with_cached_context<T, F>(settings: &Settings, f: F) -> Result<T, String>
 where
     F: FnOnce(&WhisperContext) -> Result<T, String>,
@@ -272,33 +543,36 @@ where
         wants_gpu,
     };
 
-    let cache = CONTEXT_CACHE.get_or_init(|| Mutex::new(None));
-    let mut guard = cache
+    let budget_bytes = settings.transcription.context_cache_mb as u64 * 1024 * 1024;
+    let mut guard = CONTEXT_CACHE
         .lock()
         .map_err(|_| "transcription context cache lock poisoned".to_string())?;
 
-    let should_rebuild = match guard.as_ref() {
-        Some(cached) => cached.key != key,
-        None => true,
-    };
-
-    if should_rebuild {
+    if guard.touch(&key) {
+        // Applies a budget lowered since the other contexts were loaded.
+        guard.evict(0, budget_bytes, 1);
+    } else {
+        let size_bytes = fs::metadata(&model_path)
+            .map(|meta| meta.len())
+            .unwrap_or(0);
+        // Free memory before loading so old and new models are never resident past the budget.
+        guard.evict(size_bytes, budget_bytes, 0);
         let (ctx, used_gpu) = build_with_fallback(wants_gpu, |use_gpu| {
             build_context_with_params(&model_path, use_gpu)
         })?;
-        *guard = Some(CachedContext { key, ctx, used_gpu });
+        guard.entries.push(CachedContext {
+            key,
+            model: settings.transcription.model.clone(),
+            ctx,
+            used_gpu,
+            size_bytes,
+        });
     }
 
     // If GPU init failed earlier, we cache the CPU context under the wants_gpu=true key to avoid
-    // retrying GPU on every transcription. Users can retry by toggling GPU (which should invalidate).
-    if let Some(cached) = guard.as_ref() {
-        if !cached.used_gpu && wants_gpu {
-            // Keep last GPU error visible; nothing to do here.
-        }
-    }
-
+    // retrying GPU on every transcription. Users can retry by toggling GPU (which invalidates).
     let ctx = &guard
-        .as_ref()
+        .most_recent()
         .ok_or_else(|| "Failed to build transcription context".to_string())?
         .ctx;
 
@@ -332,36 +606,12 @@ where
     builder(false).map(|value| (value, false))
 }
 
-fn transcribe_with_context(
-    ctx: &WhisperContext,
-    settings: &Settings,
-    audio: RecordedAudio,
-    thread_override: Option<u32>,
-) -> Result<String, String> {
+/// Downmix and resample to the mono 16 kHz input whisper expects.
+fn prepare_samples(audio: RecordedAudio) -> Result<Vec<f32>, String> {
     if audio.samples.is_empty() {
         return Err("No audio captured".to_string());
     }
 
-    let mut state = ctx.create_state().map_err(|err| err.to_string())?;
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-
-    if !settings.transcription.language.is_empty() {
-        params.set_language(Some(settings.transcription.language.as_str()));
-    }
-
-    let thread_count = resolve_thread_count(settings, thread_override);
-
-    params.set_n_threads(thread_count as i32);
-    params.set_print_special(false);
-    params.set_print_progress(false);
-    params.set_print_realtime(false);
-    params.set_print_timestamps(false);
-    let prompt = settings.transcription.custom_vocab.trim();
-    if !prompt.is_empty() {
-        let sanitized = prompt.replace('\0', " ");
-        params.set_initial_prompt(&sanitized);
-    }
-
     // `RecordedAudio` is already owned here, so avoid cloning the full buffer on the
     // common mono path.
     let RecordedAudio {
@@ -377,29 +627,425 @@ fn transcribe_with_context(
     };
 
     if sample_rate != TARGET_SAMPLE_RATE {
-        mono = resample_linear(&mono, sample_rate, TARGET_SAMPLE_RATE);
+        mono = resample::resample(&mono, sample_rate, TARGET_SAMPLE_RATE);
     }
 
     if mono.is_empty() {
         return Err("No usable audio after conversion".to_string());
     }
 
-    state.full(params, &mono).map_err(|err| err.to_string())?;
+    Ok(mono)
+}
 
-    let mut text = String::new();
+fn transcribe_samples(
+    ctx: &WhisperContext,
+    settings: &Settings,
+    mono: &[f32],
+    pass: Pass,
+    previous: &str,
+    cancel: Option<&AtomicBool>,
+) -> Result<TranscriptionOutput, String> {
+    let mut state = ctx.create_state().map_err(|err| err.to_string())?;
+    let decoding = &settings.transcription;
+    // Beam search multiplies decoder work; previews stay greedy so they keep up with speech.
+    let strategy = if pass == Pass::Full && decoding.beam_size > 1 {
+        SamplingStrategy::BeamSearch {
+            beam_size: decoding.beam_size as i32,
+            patience: decoding.beam_patience,
+        }
+    } else {
+        SamplingStrategy::Greedy { best_of: 1 }
+    };
+    let mut params = FullParams::new(strategy);
+    params.set_temperature(decoding.temperature.max(0.0));
+    params.set_temperature_inc(decoding.temperature_increment.max(0.0));
+    params.set_entropy_thold(decoding.entropy_threshold);
+    params.set_logprob_thold(decoding.logprob_threshold);
+    params.set_no_speech_thold(decoding.no_speech_threshold);
+
+    let thread_override = match pass {
+        Pass::Full => None,
+        Pass::Preview => Some(1),
+    };
+    let thread_count = resolve_thread_count(settings, thread_override);
+
+    params.set_n_threads(thread_count as i32);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    params.set_token_timestamps(true);
+    let translate = settings.transcription.translate;
+    if translate {
+        models::ensure_supports_translation(&settings.transcription.model)?;
+        params.set_translate(true);
+    }
+    // tinydiarize marks speaker changes with a special token; previews don't need it.
+    let speaker_turns = pass == Pass::Full
+        && decoding.detect_speakers
+        && models::supports_speaker_turns(&decoding.model);
+    if speaker_turns {
+        params.set_tdrz_enable(true);
+    }
+    let prompt = continuation::prompt(&decoding.custom_vocab, previous, |text| {
+        // Every token covers at least one byte, so this bound can't be exceeded.
+        ctx.tokenize(text, text.len() + 1)
+            .map(|tokens| tokens.len())
+            .unwrap_or_else(|_| continuation::estimate_tokens(text))
+    });
+    if !prompt.is_empty() {
+        params.set_initial_prompt(&prompt);
+    }
+
+    if let Some(cancel) = cancel {
+        // SAFETY: `cancel` is borrowed for the whole call and only read by `abort_requested`
+        // while `state.full` below is running.
+        unsafe {
+            params.set_abort_callback(Some(abort_requested));
+            params.set_abort_callback_user_data(cancel as *const AtomicBool as *mut c_void);
+        }
+    }
+
+    let requested_language = settings.transcription.language.trim();
+    let mut language_probabilities = Vec::new();
+    if requested_language.eq_ignore_ascii_case(AUTO_LANGUAGE) {
+        // English-only models cannot detect anything; whisper.cpp forces "en" for them.
+        // Previews let whisper pick the language internally instead of paying for a separate
+        // detection pass on every tick.
+        if pass == Pass::Full && ctx.is_multilingual() {
+            state
+                .pcm_to_mel(mono, thread_count as usize)
+                .map_err(|err| err.to_string())?;
+            let (lang_id, probabilities) = state
+                .lang_detect(0, thread_count as usize)
+                .map_err(|err| err.to_string())?;
+            language_probabilities = top_languages(&probabilities, LANGUAGE_PROBABILITY_LIMIT)
+                .into_iter()
+                .filter_map(|(id, probability)| {
+                    whisper_rs::get_lang_str(id).map(|language| LanguageProbability {
+                        language: language.to_string(),
+                        probability,
+                    })
+                })
+                .collect();
+            params.set_language(Some(
+                whisper_rs::get_lang_str(lang_id).unwrap_or(AUTO_LANGUAGE),
+            ));
+        } else {
+            params.set_language(Some(AUTO_LANGUAGE));
+        }
+    } else if !requested_language.is_empty() {
+        params.set_language(Some(requested_language));
+    }
+
+    if let Err(err) = state.full(params, mono) {
+        if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+            return Err(TRANSCRIPTION_CANCELLED.to_string());
+        }
+        return Err(err.to_string());
+    }
+    let language = whisper_rs::get_lang_str(state.full_lang_id_from_state()).map(str::to_string);
+
+    let token_eot = ctx.token_eot();
+    let mut candidates = Vec::new();
+    let mut turn = 0;
     for segment in state.as_iter() {
         let segment_text = segment.to_string();
         let trimmed = segment_text.trim();
+        let segment_turn = turn;
+        if speaker_turns && segment.next_segment_speaker_turn() {
+            turn += 1;
+        }
         if trimmed.is_empty() {
             continue;
         }
+
+        let mut pieces = Vec::new();
+        for index in 0..segment.n_tokens() {
+            let Some(token) = segment.get_token(index) else {
+                continue;
+            };
+            // Timestamp and control tokens all sort after end-of-text in the vocabulary.
+            if token.token_id() >= token_eot {
+                continue;
+            }
+            let data = token.token_data();
+            pieces.push(TokenPiece {
+                bytes: token.to_bytes().map(<[u8]>::to_vec).unwrap_or_default(),
+                start_ms: data.t0 * 10,
+                end_ms: data.t1 * 10,
+                probability: data.p,
+            });
+        }
+        let avg_logprob = if pieces.is_empty() {
+            0.0
+        } else {
+            pieces
+                .iter()
+                .map(|piece| piece.probability.max(f32::MIN_POSITIVE).ln())
+                .sum::<f32>()
+                / pieces.len() as f32
+        };
+        candidates.push(Candidate {
+            // whisper.cpp reports segment bounds in centiseconds.
+            segment: TranscriptSegment {
+                start_ms: segment.start_timestamp() * 10,
+                end_ms: segment.end_timestamp() * 10,
+                text: trimmed.to_string(),
+                speaker: speaker_turns.then_some(segment_turn),
+            },
+            words: group_words(pieces),
+            no_speech: segment.no_speech_probability(),
+            avg_logprob,
+        });
+    }
+
+    Ok(build_output(
+        settings,
+        candidates,
+        language,
+        language_probabilities,
+        translate,
+    ))
+}
+
+/// Run the hallucination filter (when enabled) over a backend's decoded segments and join what is
+/// left into a `TranscriptionOutput`.
+pub fn build_output(
+    settings: &Settings,
+    candidates: Vec<Candidate>,
+    language: Option<String>,
+    language_probabilities: Vec<LanguageProbability>,
+    translated: bool,
+) -> TranscriptionOutput {
+    let decoding = &settings.transcription;
+    let (candidates, filtered) = if decoding.filter_hallucinations {
+        hallucination::filter(
+            candidates,
+            decoding.no_speech_threshold,
+            decoding.logprob_threshold,
+        )
+    } else {
+        (candidates, Vec::new())
+    };
+    let mut text = String::new();
+    let mut segments = Vec::new();
+    let mut words = Vec::new();
+    for candidate in candidates {
         if !text.is_empty() {
             text.push(' ');
         }
-        text.push_str(trimmed);
+        text.push_str(&candidate.segment.text);
+        segments.push(candidate.segment);
+        words.extend(candidate.words);
     }
 
-    Ok(text)
+    TranscriptionOutput {
+        text,
+        segments,
+        words,
+        language,
+        language_probabilities,
+        translated,
+        filtered,
+    }
+}
+
+unsafe extern "C" fn abort_requested(user_data: *mut c_void) -> bool {
+    // SAFETY: `user_data` is the `AtomicBool` installed by `transcribe_samples`.
+    unsafe { (*(user_data as *const AtomicBool)).load(Ordering::Relaxed) }
+}
+
+/// Split `samples` into chunks of about `CHUNK_TARGET_SECONDS`, moving each boundary to the
+/// quietest `SILENCE_FRAME_MS` frame nearby so cuts rarely land mid-word.
+fn plan_chunks(samples: &[f32], rate: usize) -> Vec<AudioChunk> {
+    let target = CHUNK_TARGET_SECONDS * rate;
+    let search = CHUNK_SEARCH_SECONDS * rate;
+    let overlap = CHUNK_OVERLAP_MS * rate / 1000;
+    let frame = (SILENCE_FRAME_MS * rate / 1000).max(1);
+
+    let mut cuts = vec![0];
+    let mut cursor = 0;
+    while samples.len() - cursor > target + search {
+        let window = cursor + target - search..cursor + target + search;
+        let mut best = (f32::MAX, window.start + search);
+        let mut start = window.start;
+        while start + frame <= window.end {
+            let energy = samples[start..start + frame]
+                .iter()
+                .map(|sample| sample * sample)
+                .sum::<f32>();
+            if energy < best.0 {
+                best = (energy, start + frame / 2);
+            }
+            start += frame;
+        }
+        cursor = best.1;
+        cuts.push(cursor);
+    }
+    cuts.push(samples.len());
+
+    cuts.windows(2)
+        .map(|pair| AudioChunk {
+            decode: pair[0].saturating_sub(overlap)..(pair[1] + overlap).min(samples.len()),
+            keep: pair[0]..pair[1],
+        })
+        .collect()
+}
+
+/// Shift a chunk's timestamps onto the file timeline and append the segments, words and filtered
+/// segments whose midpoint falls inside the chunk's own range, dropping the copies decoded from
+/// the overlap.
+fn stitch_chunk(
+    combined: &mut TranscriptionOutput,
+    output: TranscriptionOutput,
+    chunk: &AudioChunk,
+    last: bool,
+) {
+    let to_ms = |samples: usize| (samples as u64 * 1000 / TARGET_SAMPLE_RATE as u64) as i64;
+    let offset_ms = to_ms(chunk.decode.start);
+    let keep_start_ms = to_ms(chunk.keep.start);
+    let keep_end_ms = if last {
+        i64::MAX
+    } else {
+        to_ms(chunk.keep.end)
+    };
+    let owned = |start_ms: i64, end_ms: i64| {
+        let midpoint = offset_ms + (start_ms + end_ms) / 2;
+        midpoint >= keep_start_ms && midpoint < keep_end_ms
+    };
+
+    // Turn numbers restart in every chunk; keep them distinct from the previous chunk's.
+    let turn_offset = combined
+        .segments
+        .last()
+        .and_then(|segment| segment.speaker)
+        .map_or(0, |turn| turn + 1);
+    for segment in output.segments {
+        if !owned(segment.start_ms, segment.end_ms) {
+            continue;
+        }
+        if !combined.text.is_empty() {
+            combined.text.push(' ');
+        }
+        combined.text.push_str(&segment.text);
+        combined.segments.push(TranscriptSegment {
+            start_ms: segment.start_ms + offset_ms,
+            end_ms: segment.end_ms + offset_ms,
+            text: segment.text,
+            speaker: segment.speaker.map(|turn| turn + turn_offset),
+        });
+    }
+    for word in output.words {
+        if !owned(word.start_ms, word.end_ms) {
+            continue;
+        }
+        combined.words.push(TranscriptWord {
+            start_ms: word.start_ms + offset_ms,
+            end_ms: word.end_ms + offset_ms,
+            ..word
+        });
+    }
+    for item in output.filtered {
+        if !owned(item.start_ms, item.end_ms) {
+            continue;
+        }
+        combined.filtered.push(FilteredSegment {
+            start_ms: item.start_ms + offset_ms,
+            end_ms: item.end_ms + offset_ms,
+            ..item
+        });
+    }
+}
+
+/// Pick the `limit` most likely language ids from whisper's per-language probability table.
+fn top_languages(probabilities: &[f32], limit: usize) -> Vec<(i32, f32)> {
+    let mut ranked: Vec<(i32, f32)> = probabilities
+        .iter()
+        .enumerate()
+        .filter(|(_, probability)| **probability > 0.0)
+        .map(|(id, probability)| (id as i32, *probability))
+        .collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Merge BPE tokens into words: a token with a leading space starts a new word, anything else
+/// (suffixes, punctuation) continues the current one. Bytes are joined before decoding because
+/// whisper can split multi-byte characters across tokens.
+fn group_words(pieces: Vec<TokenPiece>) -> Vec<TranscriptWord> {
+    fn finish(
+        words: &mut Vec<TranscriptWord>,
+        bytes: &[u8],
+        start_ms: i64,
+        end_ms: i64,
+        probabilities: &[f32],
+    ) {
+        let text = String::from_utf8_lossy(bytes).trim().to_string();
+        if text.is_empty() || probabilities.is_empty() {
+            return;
+        }
+        let confidence = probabilities.iter().sum::<f32>() / probabilities.len() as f32;
+        words.push(TranscriptWord {
+            start_ms,
+            end_ms,
+            text,
+            confidence,
+        });
+    }
+
+    let mut words = Vec::new();
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut probabilities: Vec<f32> = Vec::new();
+    let mut start_ms = 0;
+    let mut end_ms = 0;
+
+    for piece in pieces {
+        if piece.bytes.first() == Some(&b' ') && !bytes.is_empty() {
+            finish(&mut words, &bytes, start_ms, end_ms, &probabilities);
+            bytes.clear();
+            probabilities.clear();
+        }
+        if bytes.is_empty() {
+            start_ms = piece.start_ms;
+        }
+        bytes.extend_from_slice(&piece.bytes);
+        probabilities.push(piece.probability);
+        end_ms = piece.end_ms.max(start_ms);
+    }
+    finish(&mut words, &bytes, start_ms, end_ms, &probabilities);
+
+    words
+}
+
+/// Collapse consecutive words below `threshold` into spans so editors can jump between likely
+/// mistakes instead of individual tokens.
+pub fn low_confidence_spans(words: &[TranscriptWord], threshold: f32) -> Vec<ConfidenceSpan> {
+    let mut spans: Vec<ConfidenceSpan> = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        if word.confidence >= threshold {
+            continue;
+        }
+        match spans.last_mut() {
+            Some(span) if span.word_end == index => {
+                span.end_ms = word.end_ms;
+                span.text.push(' ');
+                span.text.push_str(&word.text);
+                span.confidence = span.confidence.min(word.confidence);
+                span.word_end = index + 1;
+            }
+            _ => spans.push(ConfidenceSpan {
+                start_ms: word.start_ms,
+                end_ms: word.end_ms,
+                text: word.text.clone(),
+                confidence: word.confidence,
+                word_start: index,
+                word_end: index + 1,
+            }),
+        }
+    }
+    spans
 }
 
 pub fn resolve_thread_count(settings: &Settings, thread_override: Option<u32>) -> u32 {
@@ -434,55 +1080,60 @@ fn to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
     mono
 }
 
-fn resample_linear(input: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if input.is_empty() || from_rate == 0 || to_rate == 0 {
-        return Vec::new();
-    }
-    if from_rate == to_rate {
-        return input.to_vec();
-    }
-
-    let ratio = to_rate as f32 / from_rate as f32;
-    let output_len = (input.len() as f32 * ratio).round() as usize;
-    let mut output = Vec::with_capacity(output_len);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    for i in 0..output_len {
-        let position = i as f32 / ratio;
-        let index = position.floor() as usize;
-        let frac = position - index as f32;
-        let s0 = input.get(index).copied().unwrap_or(0.0);
-        let s1 = input.get(index + 1).copied().unwrap_or(s0);
-        output.push(s0 + (s1 - s0) * frac);
+    fn cached(model: &str, size_bytes: u64) -> CachedContext<()> {
+        CachedContext {
+            key: ContextKey {
+                model_path: model.to_string(),
+                wants_gpu: false,
+            },
+            model: model.to_string(),
+            ctx: (),
+            used_gpu: false,
+            size_bytes,
+        }
     }
 
-    output
-}
-
-fn trim_audio(audio: RecordedAudio, max_seconds: f32) -> RecordedAudio {
-    if max_seconds <= 0.0 || audio.samples.is_empty() {
-        return audio;
+    fn models(cache: &ContextCache<()>) -> Vec<&str> {
+        cache
+            .entries
+            .iter()
+            .map(|entry| entry.model.as_str())
+            .collect()
     }
 
-    let frames = audio.samples.len() / audio.channels.max(1) as usize;
-    let max_frames = (audio.sample_rate as f32 * max_seconds).round() as usize;
-    if frames <= max_frames {
-        return audio;
-    }
+    #[test]
+    fn context_cache_evicts_least_recently_used_within_budget() {
+        let mut cache = ContextCache::new();
+        cache.entries.push(cached("tiny", 75));
+        cache.entries.push(cached("medium", 1_500));
 
-    let start_frame = frames.saturating_sub(max_frames);
-    let start_index = start_frame * audio.channels as usize;
-    let samples = audio.samples[start_index..].to_vec();
+        // Using tiny again makes medium the eviction candidate.
+        assert!(cache.touch(&cached("tiny", 0).key));
+        assert_eq!(models(&cache), vec!["medium", "tiny"]);
 
-    RecordedAudio {
-        samples,
-        sample_rate: audio.sample_rate,
-        channels: audio.channels,
+        cache.evict(460, 2_048, 0);
+        assert_eq!(models(&cache), vec!["medium", "tiny"]);
+        cache.evict(500, 2_048, 0);
+        assert_eq!(models(&cache), vec!["tiny"]);
+
+        assert!(!cache.touch(&cached("small", 0).key));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn context_cache_keeps_the_active_context_over_budget() {
+        let mut cache = ContextCache::new();
+        cache.entries.push(cached("tiny", 75));
+        cache.entries.push(cached("medium", 1_500));
+
+        cache.evict(0, 1_000, 1);
+        assert_eq!(models(&cache), vec!["medium"]);
+        cache.evict(3_000, 1_000, 0);
+        assert!(cache.entries.is_empty());
+    }
 
     static GPU_ERROR_TEST_GUARD: OnceLock<Mutex<()>> = OnceLock::new();
 
@@ -519,4 +1170,153 @@ mod tests {
         assert_eq!(result, ("ok", true));
         assert!(last_gpu_error().is_none());
     }
+
+    fn piece(text: &[u8], start_ms: i64, end_ms: i64, probability: f32) -> TokenPiece {
+        TokenPiece {
+            bytes: text.to_vec(),
+            start_ms,
+            end_ms,
+            probability,
+        }
+    }
+
+    #[test]
+    fn group_words_joins_subword_tokens() {
+        // "café" split mid-character, followed by punctuation.
+        let words = group_words(vec![
+            piece(b" Hello", 0, 400, 0.9),
+            piece(b" caf", 400, 600, 0.8),
+            piece(b"\xc3", 600, 650, 0.4),
+            piece(b"\xa9", 650, 700, 0.6),
+            piece(b".", 700, 720, 1.0),
+        ]);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!((words[0].start_ms, words[0].end_ms), (0, 400));
+        assert_eq!(words[1].text, "café.");
+        assert_eq!((words[1].start_ms, words[1].end_ms), (400, 720));
+        assert!((words[1].confidence - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn top_languages_ranks_by_probability() {
+        let ranked = top_languages(&[0.05, 0.7, 0.0, 0.2, 0.05], 2);
+        assert_eq!(ranked, vec![(1, 0.7), (3, 0.2)]);
+    }
+
+    #[test]
+    fn low_confidence_spans_merge_adjacent_words() {
+        let word = |text: &str, start_ms: i64, confidence: f32| TranscriptWord {
+            start_ms,
+            end_ms: start_ms + 100,
+            text: text.to_string(),
+            confidence,
+        };
+        let words = vec![
+            word("the", 0, 0.95),
+            word("quick", 100, 0.3),
+            word("brwn", 200, 0.2),
+            word("fox", 300, 0.9),
+            word("jumpd", 400, 0.45),
+        ];
+
+        let spans = low_confidence_spans(&words, 0.5);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].text, "quick brwn");
+        assert_eq!((spans[0].start_ms, spans[0].end_ms), (100, 300));
+        assert_eq!((spans[0].word_start, spans[0].word_end), (1, 3));
+        assert!((spans[0].confidence - 0.2).abs() < 1e-6);
+        assert_eq!(spans[1].text, "jumpd");
+        assert_eq!((spans[1].word_start, spans[1].word_end), (4, 5));
+    }
+
+    #[test]
+    fn plan_chunks_cuts_on_silence() {
+        let rate = 100;
+        // Loud audio with a quiet gap at 63s: inside the search window around the 60s target.
+        let mut samples = vec![0.5_f32; 120 * rate];
+        samples[63 * rate..63 * rate + rate / 2].fill(0.0);
+
+        let chunks = plan_chunks(&samples, rate);
+        assert_eq!(chunks.len(), 2);
+        let cut = chunks[0].keep.end;
+        assert!((63 * rate..63 * rate + rate / 2).contains(&cut));
+        assert_eq!(chunks[1].keep, cut..samples.len());
+        assert_eq!(chunks[0].decode, 0..cut + rate);
+        assert_eq!(chunks[1].decode.start, cut - rate);
+    }
+
+    #[test]
+    fn plan_chunks_keeps_short_audio_whole() {
+        let samples = vec![0.1_f32; 65 * 100];
+        let chunks = plan_chunks(&samples, 100);
+        assert_eq!(
+            chunks,
+            vec![AudioChunk {
+                decode: 0..samples.len(),
+                keep: 0..samples.len(),
+            }]
+        );
+    }
+
+    #[test]
+    fn stitch_chunk_offsets_and_drops_overlap() {
+        let rate = TARGET_SAMPLE_RATE as usize;
+        let segment = |start_ms: i64, end_ms: i64, text: &str| TranscriptSegment {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+            speaker: None,
+        };
+        let output = |segments: Vec<TranscriptSegment>| TranscriptionOutput {
+            text: String::new(),
+            segments,
+            words: Vec::new(),
+            language: None,
+            language_probabilities: Vec::new(),
+            translated: false,
+            filtered: Vec::new(),
+        };
+        let mut combined = output(Vec::new());
+
+        let first = AudioChunk {
+            decode: 0..61 * rate,
+            keep: 0..60 * rate,
+        };
+        stitch_chunk(
+            &mut combined,
+            output(vec![
+                segment(0, 30_000, "one"),
+                segment(59_000, 60_400, "two"),
+            ]),
+            &first,
+            false,
+        );
+        // The second chunk starts a second early and hears the tail of "two" again.
+        let second = AudioChunk {
+            decode: 59 * rate..90 * rate,
+            keep: 60 * rate..90 * rate,
+        };
+        stitch_chunk(
+            &mut combined,
+            output(vec![
+                segment(0, 1_400, "wo"),
+                segment(2_000, 5_000, "three"),
+            ]),
+            &second,
+            true,
+        );
+
+        assert_eq!(combined.text, "one two three");
+        let bounds: Vec<_> = combined
+            .segments
+            .iter()
+            .map(|segment| (segment.start_ms, segment.end_ms))
+            .collect();
+        assert_eq!(
+            bounds,
+            vec![(0, 30_000), (59_000, 60_400), (61_000, 64_000)]
+        );
+    }
 }
@@ -0,0 +1,318 @@
+use crate::core::phonetic::double_metaphone;
+use crate::settings::Settings;
+use crate::types::{TranscriptWord, VocabSuggestion};
+
+/// Candidates scoring below this are not worth showing.
+const SUGGESTION_FLOOR: f32 = 0.7;
+/// Shorter terms match too many ordinary words by sound.
+const MIN_TERM_LETTERS: usize = 4;
+/// How much a low word confidence raises a candidate's score (and a high one lowers it).
+const CONFIDENCE_WEIGHT: f32 = 0.3;
+/// Sound is a better guide to a misheard word than spelling.
+const PHONETIC_WEIGHT: f32 = 0.6;
+/// Transcript words a term may have been split into, beyond its own word count.
+const EXTRA_WINDOW_WORDS: usize = 1;
+
+/// Vocabulary entries from the `custom_vocab` setting: one term per line or comma.
+pub fn terms(custom_vocab: &str) -> Vec<String> {
+    custom_vocab
+        .split(['\n', ',', ';'])
+        .map(|term| term.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|term| letters(term).chars().count() >= MIN_TERM_LETTERS)
+        .collect()
+}
+
+/// `correct` with the vocabulary and threshold from settings, when vocabulary correction is on.
+pub fn correct_with_settings(
+    settings: &Settings,
+    text: String,
+    words: &[TranscriptWord],
+) -> (String, Vec<VocabSuggestion>) {
+    let config = &settings.transcription;
+    if !config.vocab_correction {
+        return (text, Vec::new());
+    }
+    let terms = terms(&config.custom_vocab);
+    correct(&text, words, &terms, config.vocab_correction_threshold)
+}
+
+/// Compare the words of `text` against the vocabulary by sound and spelling. Matches scoring at
+/// least `threshold` are rewritten to the vocabulary spelling; weaker ones are only suggested.
+/// `words` supplies per-word confidence when the text came straight from whisper.
+pub fn correct(
+    text: &str,
+    words: &[TranscriptWord],
+    terms: &[String],
+    threshold: f32,
+) -> (String, Vec<VocabSuggestion>) {
+    let tokens = tokenize(text);
+    if tokens.is_empty() || terms.is_empty() {
+        return (text.to_string(), Vec::new());
+    }
+    let confidences = align_confidence(&tokens, words);
+    let terms: Vec<Term> = terms.iter().map(|term| Term::new(term)).collect();
+
+    let mut candidates = Vec::new();
+    for start in 0..tokens.len() {
+        for term in &terms {
+            let longest = (term.words + EXTRA_WINDOW_WORDS).min(tokens.len() - start);
+            for len in term.words.saturating_sub(1).max(1)..=longest {
+                let window = &tokens[start..start + len];
+                // A match can't run across a comma or full stop.
+                if window[..len - 1]
+                    .iter()
+                    .any(|token| !token.trail.is_empty())
+                {
+                    break;
+                }
+                let confidence = confidences[start..start + len]
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .reduce(f32::min);
+                let original = &text[window[0].core_start..window[len - 1].core_end];
+                if let Some(score) = score(window, original, term, confidence) {
+                    candidates.push((start, len, score, term));
+                }
+            }
+        }
+    }
+
+    // Best candidates first; each transcript word takes part in at most one.
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2).then(b.1.cmp(&a.1)));
+    let mut taken = vec![false; tokens.len()];
+    let mut chosen = Vec::new();
+    for (start, len, score, term) in candidates {
+        if taken[start..start + len].iter().any(|taken| *taken) {
+            continue;
+        }
+        taken[start..start + len].fill(true);
+        chosen.push((start, len, score, term));
+    }
+    chosen.sort_by_key(|(start, ..)| *start);
+
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut suggestions = Vec::new();
+    for (start, len, score, term) in chosen {
+        let from = tokens[start].core_start;
+        let to = tokens[start + len - 1].core_end;
+        let original = &text[from..to];
+        let applied = score >= threshold;
+        if applied {
+            out.push_str(&text[cursor..from]);
+            out.push_str(&term.text);
+            cursor = to;
+        }
+        suggestions.push(VocabSuggestion {
+            original: original.to_string(),
+            suggestion: term.text.clone(),
+            score: (score * 100.0).round() / 100.0,
+            applied,
+        });
+    }
+    out.push_str(&text[cursor..]);
+    (out, suggestions)
+}
+
+struct Token {
+    core_start: usize,
+    core_end: usize,
+    /// Lowercase letters and digits of the word.
+    key: String,
+    /// Punctuation after the word.
+    trail: String,
+}
+
+struct Term {
+    text: String,
+    key: String,
+    words: usize,
+    keys: (String, String),
+}
+
+impl Term {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            key: letters(text),
+            words: text.split_whitespace().count(),
+            keys: double_metaphone(text),
+        }
+    }
+}
+
+fn letters(text: &str) -> String {
+    text.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += word.len();
+        let word = word.trim_end();
+        let Some(core_start) = word.find(char::is_alphanumeric) else {
+            continue;
+        };
+        let core_end = word
+            .char_indices()
+            .rev()
+            .find(|(_, ch)| ch.is_alphanumeric())
+            .map_or(core_start, |(index, ch)| index + ch.len_utf8());
+        tokens.push(Token {
+            core_start: start + core_start,
+            core_end: start + core_end,
+            key: letters(&word[core_start..core_end]),
+            trail: word[core_end..].to_string(),
+        });
+    }
+    tokens
+}
+
+/// Pair each token with the whisper word it came from, when there is one nearby with the same
+/// letters. Tokens added or changed after decoding get no confidence.
+fn align_confidence(tokens: &[Token], words: &[TranscriptWord]) -> Vec<Option<f32>> {
+    const LOOKAHEAD: usize = 4;
+    let mut next = 0;
+    tokens
+        .iter()
+        .map(|token| {
+            let found = words
+                .iter()
+                .enumerate()
+                .skip(next)
+                .take(LOOKAHEAD)
+                .find(|(_, word)| letters(&word.text) == token.key)?;
+            next = found.0 + 1;
+            Some(found.1.confidence)
+        })
+        .collect()
+}
+
+fn score(window: &[Token], original: &str, term: &Term, confidence: Option<f32>) -> Option<f32> {
+    let spoken: String = window.iter().map(|token| token.key.as_str()).collect();
+    if spoken == term.key {
+        // Only the spelling differs ("postgrest", "Post-Grest"); nothing is guessed.
+        return (original != term.text).then_some(1.0);
+    }
+    // The term itself plus a neighbouring word is not a misspelling of it.
+    if window.iter().any(|token| token.key == term.key) {
+        return None;
+    }
+    let (spoken_chars, term_chars) = (spoken.chars().count(), term.key.chars().count());
+    if spoken_chars * 2 < term_chars || spoken_chars > term_chars * 2 {
+        return None;
+    }
+    let keys = double_metaphone(&spoken);
+    if keys.0.chars().next() != term.keys.0.chars().next() {
+        return None;
+    }
+    let phonetic = [
+        (&keys.0, &term.keys.0),
+        (&keys.0, &term.keys.1),
+        (&keys.1, &term.keys.0),
+        (&keys.1, &term.keys.1),
+    ]
+    .into_iter()
+    .map(|(a, b)| similarity(a, b))
+    .fold(0.0, f32::max);
+    let spelling = similarity(&spoken, &term.key);
+    let mut score = PHONETIC_WEIGHT * phonetic + (1.0 - PHONETIC_WEIGHT) * spelling;
+    if let Some(confidence) = confidence {
+        score += CONFIDENCE_WEIGHT * (0.5 - confidence);
+    }
+    let score = score.clamp(0.0, 1.0);
+    (score >= SUGGESTION_FLOOR).then_some(score)
+}
+
+/// 1 minus the edit distance relative to the longer string.
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f32 / longest as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> Vec<String> {
+        terms("Kubernetes, PostgREST\nkubectl; Ana")
+    }
+
+    fn word(text: &str, confidence: f32) -> TranscriptWord {
+        TranscriptWord {
+            start_ms: 0,
+            end_ms: 0,
+            text: text.to_string(),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn parses_terms_and_skips_short_ones() {
+        assert_eq!(vocab(), vec!["Kubernetes", "PostgREST", "kubectl"]);
+    }
+
+    #[test]
+    fn rewrites_confident_matches_and_suggests_the_rest() {
+        let (text, suggestions) = correct(
+            "We moved postgrest onto cooper netties.",
+            &[],
+            &vocab(),
+            0.85,
+        );
+        assert_eq!(text, "We moved PostgREST onto cooper netties.");
+        assert_eq!(suggestions.len(), 2);
+        assert!(suggestions[0].applied);
+        assert_eq!(suggestions[1].original, "cooper netties");
+        assert_eq!(suggestions[1].suggestion, "Kubernetes");
+        assert!(!suggestions[1].applied);
+    }
+
+    #[test]
+    fn low_word_confidence_tips_a_match_over_the_threshold() {
+        let text = "We moved it to cooper netties";
+        let confident =
+            ["We", "moved", "it", "to", "cooper", "netties"].map(|text| word(text, 0.95));
+        let (unchanged, _) = correct(text, &confident, &vocab(), 0.85);
+        assert_eq!(unchanged, text);
+
+        let mut unsure = confident.to_vec();
+        unsure[4].confidence = 0.2;
+        unsure[5].confidence = 0.3;
+        let (fixed, suggestions) = correct(text, &unsure, &vocab(), 0.85);
+        assert_eq!(fixed, "We moved it to Kubernetes");
+        assert!(suggestions[0].applied);
+    }
+
+    #[test]
+    fn leaves_ordinary_words_and_correct_spellings_alone() {
+        let (text, suggestions) = correct(
+            "Kubernetes governance and the kubectl cube.",
+            &[],
+            &vocab(),
+            0.85,
+        );
+        assert_eq!(text, "Kubernetes governance and the kubectl cube.");
+        assert!(suggestions.is_empty());
+    }
+}
@@ -0,0 +1,394 @@
+use std::fs;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+use crate::app_tray;
+use crate::commands;
+use crate::core::audio::CapturedAudio;
+use crate::core::continuation::PreviousDictation;
+use crate::core::streaming::{self, StreamPrefix};
+use crate::core::transcription::TranscriptionOutput;
+use crate::core::{
+    audio_import, continuation, dictation, embedding, itn, replacements, storage, summary,
+    transcription, vocabulary,
+};
+use crate::settings::Settings;
+use crate::state::AppState;
+use crate::tray;
+use crate::types::{
+    AppliedReplacement, JobStatus, ReplacementRule, Transcript, TranscriptionJob, VocabSuggestion,
+};
+
+/// Finished jobs kept in `AppState::jobs` so the UI can show recent failures.
+const FINISHED_JOB_LIMIT: usize = 20;
+
+/// A recording that has just stopped.
+pub struct StoppedRecording {
+    pub audio: CapturedAudio,
+    pub started_at_ms: i64,
+    /// Time spent recording, not counting pauses.
+    pub duration_ms: u32,
+    /// Window focused when the recording started; the paste goes back there.
+    pub focus_window: Option<String>,
+    /// Live-preview words already committed for this recording.
+    pub prefix: Option<StreamPrefix>,
+}
+
+/// A stopped recording waiting for the transcription worker.
+pub struct JobRequest {
+    app: AppHandle,
+    job_id: String,
+    settings: Settings,
+    recording: StoppedRecording,
+    stopped_at_ms: i64,
+}
+
+/// Start the transcription worker. Jobs run one at a time in submission order so dictations are
+/// pasted in the order they were spoken, while the recorder is free to start again immediately.
+pub fn start_worker() -> Sender<JobRequest> {
+    let (tx, rx) = mpsc::channel::<JobRequest>();
+    thread::spawn(move || {
+        for request in rx {
+            run_job(request);
+        }
+    });
+    tx
+}
+
+/// Queue `recording` for transcription and return the job as first reported to the UI.
+pub fn enqueue(
+    app: &AppHandle,
+    state: &Mutex<AppState>,
+    settings: Settings,
+    recording: StoppedRecording,
+) -> Result<TranscriptionJob, String> {
+    let job = TranscriptionJob {
+        id: Uuid::new_v4().to_string(),
+        status: JobStatus::Queued,
+        created_at: now_ms(),
+        duration_ms: recording.duration_ms,
+        transcript_id: None,
+        error: None,
+    };
+    let job_tx = {
+        let mut guard = state
+            .lock()
+            .map_err(|_| "state lock poisoned".to_string())?;
+        guard.jobs.push(job.clone());
+        guard.job_tx.clone()
+    };
+    // Announce the job before the worker can pick it up so "queued" never arrives after "running".
+    emit_job_event(app, &job);
+
+    let request = JobRequest {
+        app: app.clone(),
+        job_id: job.id.clone(),
+        settings,
+        recording,
+        // Jobs are queued as the recording stops.
+        stopped_at_ms: job.created_at,
+    };
+    if job_tx.send(request).is_err() {
+        let err = "Transcription worker is not running".to_string();
+        update_job(app, state, &job.id, |job| {
+            job.status = JobStatus::Failed;
+            job.error = Some(err.clone());
+        });
+        return Err(err);
+    }
+
+    Ok(job)
+}
+
+fn run_job(request: JobRequest) {
+    let JobRequest {
+        app,
+        job_id,
+        settings,
+        recording:
+            StoppedRecording {
+                audio,
+                started_at_ms,
+                duration_ms,
+                focus_window,
+                prefix,
+            },
+        stopped_at_ms,
+    } = request;
+    let state = app.state::<Mutex<AppState>>();
+    let state = state.inner();
+
+    update_job(&app, state, &job_id, |job| job.status = JobStatus::Running);
+
+    // Jobs run in order, so the previous dictation is finished by the time this one starts.
+    let previous = state
+        .lock()
+        .ok()
+        .and_then(|guard| guard.previous_dictation.clone())
+        .filter(|previous| {
+            settings.transcription.continuation_context
+                && continuation::continues(
+                    previous,
+                    started_at_ms,
+                    focus_window.as_deref(),
+                    settings.transcription.continuation_window_secs,
+                )
+        })
+        .map(|previous| previous.text)
+        .unwrap_or_default();
+
+    match transcribe_job(state, &settings, audio, duration_ms, prefix, &previous) {
+        Ok(transcript) => {
+            if let (false, Ok(mut guard)) = (transcript.text.trim().is_empty(), state.lock()) {
+                guard.previous_dictation = Some(PreviousDictation {
+                    text: transcript.text.clone(),
+                    ended_at_ms: stopped_at_ms,
+                    focus_window: focus_window.clone(),
+                });
+            }
+            update_job(&app, state, &job_id, |job| {
+                job.status = JobStatus::Done;
+                job.transcript_id = Some(transcript.id.clone());
+            });
+            commands::emit_transcript_event(&app, &Some(transcript.clone()));
+            app_tray::refresh_tray(&app, state);
+            // Everything was filtered as a hallucination: keep the record, but paste nothing.
+            if transcript.text.trim().is_empty() {
+                return;
+            }
+
+            let automation_settings = state
+                .lock()
+                .map(|guard| guard.settings.automation.clone())
+                .unwrap_or_else(|_| settings.automation.clone());
+            // Runs on the worker so the next job's paste can't overtake this one.
+            commands::run_automation(
+                &app,
+                &transcript,
+                &automation_settings,
+                focus_window.as_deref(),
+            );
+        }
+        Err(err) => {
+            if let Ok(guard) = state.lock() {
+                let _ = tray::write_error(&settings, &guard.transcripts, &err);
+            }
+            update_job(&app, state, &job_id, |job| {
+                job.status = JobStatus::Failed;
+                job.error = Some(err.clone());
+            });
+        }
+    }
+}
+
+fn transcribe_job(
+    state: &Mutex<AppState>,
+    settings: &Settings,
+    audio: CapturedAudio,
+    duration_ms: u32,
+    prefix: Option<StreamPrefix>,
+    previous: &str,
+) -> Result<Transcript, String> {
+    // A failed spooled recording stays on disk so hours of audio aren't lost with the error.
+    let (audio, spool) = match audio {
+        CapturedAudio::Memory(audio) => (audio, None),
+        CapturedAudio::Spooled(path) => {
+            let decoded = audio_import::decode_audio_file(&path)
+                .map_err(|err| format!("{err} (recording kept at {})", path.display()))?;
+            (decoded.audio, Some(path))
+        }
+    };
+    let audio_for_save = if settings.storage.keep_audio && spool.is_none() {
+        Some(audio.clone())
+    } else {
+        None
+    };
+    // Speaker detection needs the whole recording decoded and analysed in one pass, and a
+    // spooled recording is far longer than the preview could follow. The preview decodes
+    // greedily, so its words can't stand in for a beam search either.
+    let config = &settings.transcription;
+    let prefix =
+        prefix.filter(|_| !config.detect_speakers && config.beam_size <= 1 && spool.is_none());
+    let output = match (prefix, &spool) {
+        (Some(prefix), _) => streaming::transcribe_remainder(settings, audio, prefix, previous)?,
+        (None, Some(path)) => transcription::transcribe_chunked(
+            settings,
+            audio,
+            previous,
+            &AtomicBool::new(false),
+            |_| {},
+        )
+        .map_err(|err| format!("{err} (recording kept at {})", path.display()))?,
+        (None, None) => transcription::transcribe(settings, audio, previous)?,
+    };
+    // Whisper already succeeded; a broken rules table must not cost the user the transcript.
+    let rules = storage::load_replacement_rules(settings).unwrap_or_else(|err| {
+        eprintln!("Skipping replacement rules: {err}");
+        Vec::new()
+    });
+    let (text, vocab_suggestions, applied) = finish_text(settings, &rules, &output);
+    let created_at = now_ms();
+
+    let title = summary::generate_title(&text);
+    let summary = summary::generate_summary(&text);
+    let embedding = embedding::embed_text(&text);
+    let id = Uuid::new_v4().to_string();
+    let audio_path = if let Some(audio) = audio_for_save {
+        storage::save_audio_recording(settings, &id, &audio)
+            .ok()
+            .map(|path| path.to_string_lossy().to_string())
+    } else if let Some(spool) = spool {
+        if settings.storage.keep_audio {
+            storage::keep_spooled_audio(settings, &id, &spool)
+                .ok()
+                .map(|path| path.to_string_lossy().to_string())
+        } else {
+            let _ = fs::remove_file(&spool);
+            None
+        }
+    } else {
+        None
+    };
+    let transcript = Transcript {
+        id,
+        created_at,
+        duration_ms,
+        text,
+        title,
+        summary,
+        tags: Vec::new(),
+        audio_path,
+        segments: output.segments,
+        words: output.words,
+        language: output.language,
+        language_probabilities: output.language_probabilities,
+        translated: output.translated,
+        filtered: output.filtered,
+        speaker_names: Vec::new(),
+        replacements: applied,
+        vocab_suggestions,
+        embedding: Some(embedding),
+    };
+
+    let mut guard = state
+        .lock()
+        .map_err(|_| "state lock poisoned".to_string())?;
+    guard.transcripts.insert(0, transcript.clone());
+    if let Err(err) = storage::upsert_transcript(&guard.settings, &transcript) {
+        guard.transcripts.retain(|item| item.id != transcript.id);
+        return Err(err);
+    }
+    let _ = tray::write_recents(&guard.settings, &guard.transcripts, Some(created_at));
+
+    Ok(transcript)
+}
+
+/// Post-processing for every transcript, dictated or imported: vocabulary correction, dictation
+/// commands, written numbers, then replacement `rules`.
+pub fn finish_text(
+    settings: &Settings,
+    rules: &[ReplacementRule],
+    output: &TranscriptionOutput,
+) -> (String, Vec<VocabSuggestion>, Vec<AppliedReplacement>) {
+    let (mut text, vocab_suggestions) =
+        vocabulary::correct_with_settings(settings, output.text.clone(), &output.words);
+    if let Some(language) = output.text_language(settings).as_deref() {
+        if dictation::enabled(settings, language) {
+            match storage::load_dictation_commands(settings, Some(language)) {
+                Ok(commands) => text = dictation::apply(&text, &commands),
+                Err(err) => eprintln!("Skipping dictation commands: {err}"),
+            }
+        }
+        if settings.transcription.inverse_text_normalization {
+            text = itn::normalize(&text, language);
+        }
+    }
+    let (text, applied) = replacements::apply(&text, rules);
+    (text, vocab_suggestions, applied)
+}
+
+/// Apply `change` to the job, drop the oldest finished jobs past `FINISHED_JOB_LIMIT`, and emit
+/// the updated job.
+fn update_job<F>(app: &AppHandle, state: &Mutex<AppState>, job_id: &str, change: F)
+where
+    F: FnOnce(&mut TranscriptionJob),
+{
+    let updated = {
+        let Ok(mut guard) = state.lock() else {
+            return;
+        };
+        let Some(job) = guard.jobs.iter_mut().find(|job| job.id == job_id) else {
+            return;
+        };
+        change(job);
+        let updated = job.clone();
+        prune_finished(&mut guard.jobs, FINISHED_JOB_LIMIT);
+        updated
+    };
+    emit_job_event(app, &updated);
+}
+
+fn prune_finished(jobs: &mut Vec<TranscriptionJob>, limit: usize) {
+    let finished = jobs.iter().filter(|job| is_finished(job)).count();
+    let mut excess = finished.saturating_sub(limit);
+    jobs.retain(|job| {
+        if excess > 0 && is_finished(job) {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+fn is_finished(job: &TranscriptionJob) -> bool {
+    matches!(job.status, JobStatus::Done | JobStatus::Failed)
+}
+
+fn emit_job_event(app: &AppHandle, job: &TranscriptionJob) {
+    let _ = app.emit("transcription-job", job);
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, status: JobStatus) -> TranscriptionJob {
+        TranscriptionJob {
+            id: id.to_string(),
+            status,
+            created_at: 0,
+            duration_ms: 0,
+            transcript_id: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn prune_finished_keeps_active_jobs() {
+        let mut jobs = vec![
+            job("a", JobStatus::Done),
+            job("b", JobStatus::Failed),
+            job("c", JobStatus::Running),
+            job("d", JobStatus::Done),
+            job("e", JobStatus::Queued),
+        ];
+
+        prune_finished(&mut jobs, 1);
+
+        let ids: Vec<_> = jobs.iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "d", "e"]);
+    }
+}
@@ -3,6 +3,7 @@ mod cli;
 mod commands;
 mod core;
 mod hud;
+mod jobs;
 mod overlay;
 mod settings;
 mod state;
@@ -34,7 +35,7 @@ fn main() {
         .plugin(tauri_plugin_process::init())
         .manage(Mutex::new(state::AppState::load()))
         .setup(move |app| {
-            let _ = overlay::write_state(false, None, Some(0.0));
+            let _ = overlay::write_state(false, false, None, Some(0.0));
             let _ = hud::ensure_recording_hud(app);
             if let Ok(guard) = app.state::<Mutex<state::AppState>>().lock() {
                 let last_transcript_at_ms = guard.transcripts.first().map(|item| item.created_at);
@@ -73,13 +74,21 @@ fn main() {
             commands::save_settings,
             commands::list_transcripts,
             commands::search_transcripts,
+            commands::get_low_confidence_spans,
             commands::import_audio_files,
+            commands::cancel_transcription,
             commands::update_transcript,
             commands::delete_transcript,
             commands::clear_transcripts,
             commands::list_clips,
             commands::create_clip,
             commands::delete_clip,
+            commands::list_dictation_commands,
+            commands::save_dictation_command,
+            commands::delete_dictation_command,
+            commands::list_replacement_rules,
+            commands::save_replacement_rule,
+            commands::delete_replacement_rule,
             commands::get_runtime_info,
             commands::get_macos_permissions,
             commands::request_macos_accessibility_permission,
@@ -87,15 +96,24 @@ fn main() {
             commands::open_macos_permission_settings,
             commands::get_performance_info,
             commands::benchmark_transcription,
+            commands::list_benchmarks,
+            commands::evaluate_transcription,
+            commands::list_evaluations,
             commands::copy_text,
             commands::export_transcript,
             commands::check_for_updates,
             commands::get_storage_stats,
             commands::list_audio_devices,
             commands::toggle_recording,
+            commands::list_transcription_jobs,
             commands::get_recording_level,
             commands::get_recording_state,
             commands::paste_last_transcript,
+            commands::start_recording,
+            commands::stop_recording,
+            commands::pause_recording,
+            commands::resume_recording,
+            commands::toggle_pause,
             commands::list_models,
             commands::download_model,
             commands::delete_model,
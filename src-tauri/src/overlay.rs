@@ -2,6 +2,7 @@
 mod linux_overlay {
     use std::fs;
     use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::{Mutex, OnceLock};
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -23,19 +24,27 @@ mod linux_overlay {
     struct OverlayWriteCache {
         initialized: bool,
         last_recording: bool,
+        last_paused: bool,
+        last_mic_warm: bool,
         last_started_at_ms: Option<i64>,
         last_level_q: i32,
         last_write_at_ms: i64,
     }
 
     static OVERLAY_CACHE: OnceLock<Mutex<OverlayWriteCache>> = OnceLock::new();
+    static MIC_WARM: AtomicBool = AtomicBool::new(false);
 
     #[derive(Serialize)]
     struct OverlayState {
         recording: bool,
+        /// Recording but not keeping audio; the clock should hold still.
+        paused: bool,
+        /// Moved forward by finished pauses, so time since it is time recorded.
         started_at_ms: Option<i64>,
         updated_at_ms: i64,
         level: Option<f32>,
+        /// The microphone is open between recordings, holding a pre-roll.
+        mic_warm: bool,
     }
 
     fn now_ms() -> i64 {
@@ -60,6 +69,8 @@ mod linux_overlay {
     fn should_write(
         cache: &OverlayWriteCache,
         recording: bool,
+        paused: bool,
+        mic_warm: bool,
         started_at_ms: Option<i64>,
         level_q: i32,
         now_ms: i64,
@@ -67,7 +78,10 @@ mod linux_overlay {
         if !cache.initialized {
             return true;
         }
-        if cache.last_recording != recording {
+        if cache.last_recording != recording
+            || cache.last_paused != paused
+            || cache.last_mic_warm != mic_warm
+        {
             return true;
         }
         if cache.last_started_at_ms != started_at_ms {
@@ -105,18 +119,28 @@ mod linux_overlay {
 
     pub fn write_state(
         recording: bool,
+        paused: bool,
         started_at_ms: Option<i64>,
         level: Option<f32>,
     ) -> Result<(), String> {
         let now = now_ms();
         let level_q = quantize_level(level);
+        let mic_warm = MIC_WARM.load(Ordering::Relaxed);
 
         let cache_lock = OVERLAY_CACHE.get_or_init(|| Mutex::new(OverlayWriteCache::default()));
         {
             let cache = cache_lock
                 .lock()
                 .map_err(|_| "overlay cache lock poisoned".to_string())?;
-            if !should_write(&cache, recording, started_at_ms, level_q, now) {
+            if !should_write(
+                &cache,
+                recording,
+                paused,
+                mic_warm,
+                started_at_ms,
+                level_q,
+                now,
+            ) {
                 return Ok(());
             }
         }
@@ -128,9 +152,11 @@ mod linux_overlay {
 
         let state = OverlayState {
             recording,
+            paused,
             started_at_ms,
             updated_at_ms: now,
             level,
+            mic_warm,
         };
         let payload = serde_json::to_string(&state).map_err(|err| err.to_string())?;
         let tmp_path = path.with_extension("tmp");
@@ -141,6 +167,8 @@ mod linux_overlay {
             *cache = OverlayWriteCache {
                 initialized: true,
                 last_recording: recording,
+                last_paused: paused,
+                last_mic_warm: mic_warm,
                 last_started_at_ms: started_at_ms,
                 last_level_q: level_q,
                 last_write_at_ms: now,
@@ -150,6 +178,25 @@ mod linux_overlay {
         Ok(())
     }
 
+    /// Report whether the microphone is kept open between recordings, keeping the rest of the
+    /// last written state.
+    pub fn set_mic_warm(warm: bool) -> Result<(), String> {
+        MIC_WARM.store(warm, Ordering::Relaxed);
+        let cache = OVERLAY_CACHE
+            .get_or_init(|| Mutex::new(OverlayWriteCache::default()))
+            .lock()
+            .map(|cache| *cache)
+            .map_err(|_| "overlay cache lock poisoned".to_string())?;
+        let level = (cache.last_level_q >= 0)
+            .then(|| cache.last_level_q as f32 / OVERLAY_LEVEL_QUANTIZE_STEPS as f32);
+        write_state(
+            cache.last_recording,
+            cache.last_paused,
+            cache.last_started_at_ms,
+            level,
+        )
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -159,22 +206,30 @@ mod linux_overlay {
             let cache = OverlayWriteCache {
                 initialized: true,
                 last_recording: true,
+                last_paused: false,
+                last_mic_warm: false,
                 last_started_at_ms: Some(1),
                 last_level_q: 50,
                 last_write_at_ms: 1000,
             };
 
             // Too soon: no write even if level changes a bit.
-            assert!(!should_write(&cache, true, Some(1), 52, 1100));
+            assert!(!should_write(&cache, true, false, false, Some(1), 52, 1100));
 
             // After min interval but below delta: no write.
-            assert!(!should_write(&cache, true, Some(1), 51, 1300));
+            assert!(!should_write(&cache, true, false, false, Some(1), 51, 1300));
 
             // After min interval and delta exceeded: write.
-            assert!(should_write(&cache, true, Some(1), 55, 1300));
+            assert!(should_write(&cache, true, false, false, Some(1), 55, 1300));
 
             // Keepalive.
-            assert!(should_write(&cache, true, Some(1), 50, 2100));
+            assert!(should_write(&cache, true, false, false, Some(1), 50, 2100));
+
+            // Pausing is a state change.
+            assert!(should_write(&cache, true, true, false, Some(1), 0, 1100));
+
+            // So is the mic warming up between recordings.
+            assert!(should_write(&cache, true, false, true, Some(1), 50, 1100));
         }
 
         #[test]
@@ -182,23 +237,31 @@ mod linux_overlay {
             let cache = OverlayWriteCache {
                 initialized: true,
                 last_recording: false,
+                last_paused: false,
+                last_mic_warm: false,
                 last_started_at_ms: None,
                 last_level_q: -1,
                 last_write_at_ms: 1000,
             };
-            assert!(should_write(&cache, true, Some(123), 0, 1010));
+            assert!(should_write(&cache, true, false, false, Some(123), 0, 1010));
         }
     }
 }
 
 #[cfg(target_os = "linux")]
-pub use linux_overlay::write_state;
+pub use linux_overlay::{set_mic_warm, write_state};
 
 #[cfg(not(target_os = "linux"))]
 pub fn write_state(
     _recording: bool,
+    _paused: bool,
     _started_at_ms: Option<i64>,
     _level: Option<f32>,
 ) -> Result<(), String> {
     Ok(())
 }
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_mic_warm(_warm: bool) -> Result<(), String> {
+    Ok(())
+}
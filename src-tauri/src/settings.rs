@@ -12,7 +12,7 @@ pub struct Settings {
     pub ui: UiSettings,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AudioSettings {
     pub input_device_id: String,
     pub sample_rate_hz: u32,
@@ -24,6 +24,19 @@ pub struct AudioSettings {
     pub vad_threshold: f32,
     pub vad_silence_ms: u32,
     pub vad_resume_ms: u32,
+    /// Write recordings to a spool file in the data dir as they are captured, so they can run
+    /// past `MAX_RECORDING_SECONDS` without losing their start.
+    pub long_recording: bool,
+    /// End the recording by itself once the speaker goes quiet, as if stopped by hand.
+    pub auto_stop: bool,
+    /// Silence after speech that ends an auto-stop recording.
+    pub auto_stop_silence_ms: u32,
+    /// An auto-stop recording in which nobody speaks ends after this long.
+    pub auto_stop_no_speech_ms: u32,
+    /// Keep the microphone open between recordings and start each one with the audio from just
+    /// before it was triggered. The pre-roll only ever lives in memory.
+    pub pre_roll: bool,
+    pub pre_roll_ms: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,16 +44,91 @@ pub struct HotkeySettings {
     pub record_toggle: String,
     pub paste_last: String,
     pub open_app: String,
+    /// Optional hotkey that toggles a recording transcribed with translation forced on.
+    /// Empty disables it.
+    pub record_translate: String,
+    /// Optional hotkey that pauses and resumes the current recording. Empty disables it.
+    pub pause_toggle: String,
+    /// Hold the record hotkey to record and release it to transcribe, instead of toggling.
+    pub push_to_talk: bool,
+    /// In push-to-talk mode, shorter presses are taken as accidental and discarded.
+    pub push_to_talk_min_hold_ms: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionSettings {
+    /// "whisper" runs the local model; "http" sends audio to `server_url`.
+    pub backend: String,
+    /// whisper.cpp server `/inference` or OpenAI-compatible `/v1/audio/transcriptions` endpoint.
+    pub server_url: String,
+    /// Model name sent to the server; empty lets the server use its default.
+    pub server_model: String,
     pub model: String,
     pub model_dir: String,
     pub threads: u32,
     pub language: String,
+    /// Translate speech into English instead of transcribing in the source language.
+    /// Requires a multilingual model.
+    pub translate: bool,
     pub custom_vocab: String,
+    /// Compare transcripts against `custom_vocab` by sound and suggest or apply its spellings.
+    pub vocab_correction: bool,
+    /// Score (0-1) from which a vocabulary match is rewritten rather than only suggested.
+    pub vocab_correction_threshold: f32,
     pub use_gpu: bool,
+    /// Memory budget for loaded models. Recently used models stay loaded while they fit; the
+    /// active model is always kept.
+    pub context_cache_mb: u32,
+    /// "fast", "accurate" or "custom". Named presets overwrite the decoding fields below on save.
+    pub decoding_preset: String,
+    /// 1 decodes greedily; larger values enable beam search with that many beams.
+    pub beam_size: u32,
+    pub beam_patience: f32,
+    pub temperature: f32,
+    /// Step added to the temperature when a decode fails the thresholds below; 0 disables
+    /// fallback.
+    pub temperature_increment: f32,
+    pub entropy_threshold: f32,
+    pub logprob_threshold: f32,
+    pub no_speech_threshold: f32,
+    /// Drop known hallucinated phrases, repetition loops and silent segments from the output.
+    pub filter_hallucinations: bool,
+    /// Label segments by speaker: from speaker-turn tokens on tinydiarize models, otherwise by
+    /// clustering the voices.
+    pub detect_speakers: bool,
+    /// Languages in which spoken commands ("new line", "comma", "scratch that") are interpreted.
+    pub dictation_languages: Vec<String>,
+    /// Rewrite spoken numbers, currency, dates and times in written form ("$25 on March 3").
+    pub inverse_text_normalization: bool,
+    /// Prompt a dictation with the end of the previous one when it follows within
+    /// `continuation_window_secs` or goes into the same window, so names and casing carry over.
+    pub continuation_context: bool,
+    pub continuation_window_secs: u32,
+}
+
+pub const TRANSCRIPTION_BACKEND_WHISPER: &str = "whisper";
+pub const TRANSCRIPTION_BACKEND_HTTP: &str = "http";
+pub const DECODING_PRESET_FAST: &str = "fast";
+pub const DECODING_PRESET_ACCURATE: &str = "accurate";
+
+impl TranscriptionSettings {
+    /// Fill the decoding fields from `decoding_preset`. Unknown names (including "custom") keep
+    /// the user's values.
+    pub fn apply_decoding_preset(&mut self) {
+        let beam_size = match self.decoding_preset.as_str() {
+            DECODING_PRESET_FAST => 1,
+            DECODING_PRESET_ACCURATE => 5,
+            _ => return,
+        };
+        // Thresholds match whisper.cpp's defaults; the presets only trade speed for beams.
+        self.beam_size = beam_size;
+        self.beam_patience = 1.0;
+        self.temperature = 0.0;
+        self.temperature_increment = 0.2;
+        self.entropy_threshold = 2.4;
+        self.logprob_threshold = -1.0;
+        self.no_speech_threshold = 0.6;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,20 +236,51 @@ impl Default for Settings {
                 vad_threshold: 0.02,
                 vad_silence_ms: 800,
                 vad_resume_ms: 200,
+                long_recording: false,
+                auto_stop: false,
+                auto_stop_silence_ms: 2000,
+                auto_stop_no_speech_ms: 10_000,
+                pre_roll: false,
+                pre_roll_ms: 500,
             },
             hotkeys: HotkeySettings {
                 // Avoid macOS reserved Option+Command+Space (Spotlight / Finder search).
                 record_toggle: "CommandOrControl+Shift+Space".to_string(),
                 paste_last: "CommandOrControl+Alt+V".to_string(),
                 open_app: "CommandOrControl+Alt+O".to_string(),
+                record_translate: String::new(),
+                pause_toggle: String::new(),
+                push_to_talk: false,
+                push_to_talk_min_hold_ms: 300,
             },
             transcription: TranscriptionSettings {
+                backend: TRANSCRIPTION_BACKEND_WHISPER.to_string(),
+                server_url: "http://127.0.0.1:8080/inference".to_string(),
+                server_model: String::new(),
                 model: "small.en".to_string(),
                 model_dir: default_model_dir(),
                 threads: 0,
                 language: "en".to_string(),
+                translate: false,
                 custom_vocab: String::new(),
+                vocab_correction: true,
+                vocab_correction_threshold: 0.85,
                 use_gpu: false,
+                context_cache_mb: 2048,
+                decoding_preset: DECODING_PRESET_FAST.to_string(),
+                beam_size: 1,
+                beam_patience: 1.0,
+                temperature: 0.0,
+                temperature_increment: 0.2,
+                entropy_threshold: 2.4,
+                logprob_threshold: -1.0,
+                no_speech_threshold: 0.6,
+                filter_hallucinations: true,
+                detect_speakers: false,
+                dictation_languages: Vec::new(),
+                inverse_text_normalization: false,
+                continuation_context: false,
+                continuation_window_secs: 60,
             },
             automation: AutomationSettings {
                 auto_paste_enabled: true,
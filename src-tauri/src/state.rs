@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Instant;
@@ -5,9 +6,12 @@ use std::time::Instant;
 use std::sync::mpsc::Sender;
 
 use crate::core::audio::{self, AudioCommand};
+use crate::core::continuation::PreviousDictation;
 use crate::core::storage::{load_clips, load_settings, load_transcripts_with_retention};
+use crate::core::streaming::StreamPrefix;
+use crate::jobs::{self, JobRequest};
 use crate::settings::Settings;
-use crate::types::{Clip, Transcript};
+use crate::types::{Clip, Transcript, TranscriptionJob, TranscriptionTask};
 
 pub struct AppState {
     pub settings: Settings,
@@ -16,10 +20,24 @@ pub struct AppState {
     pub recording: bool,
     pub recording_started_at: Option<Instant>,
     pub recording_started_at_ms: Option<i64>,
+    /// The current recording is paused: the stream stays open but no audio is kept.
+    pub recording_paused: bool,
     pub preview_cancel: Option<Arc<AtomicBool>>,
+    /// Committed live-preview words for the current recording, reused by its final transcription.
+    pub preview_prefix: Option<StreamPrefix>,
     pub ui_active: Arc<AtomicBool>,
     pub audio_tx: Sender<AudioCommand>,
+    pub job_tx: Sender<JobRequest>,
+    /// Queued, running, and recently finished transcription jobs, oldest first.
+    pub jobs: Vec<TranscriptionJob>,
     pub last_focus_window: Option<String>,
+    /// Per-recording translate override from the hotkey/CLI action that started it.
+    pub recording_translate: Option<bool>,
+    /// Cancel flags of the file transcriptions running now, keyed by a per-run id so each run
+    /// removes only its own; `cancel_transcription` sets them.
+    pub transcription_cancels: HashMap<String, (TranscriptionTask, Arc<AtomicBool>)>,
+    /// Last dictation the job worker finished, for continuation prompts.
+    pub previous_dictation: Option<PreviousDictation>,
 }
 
 impl AppState {
@@ -28,6 +46,8 @@ impl AppState {
         let transcripts = load_transcripts_with_retention(&settings);
         let clips = load_clips(&settings);
         let audio_tx = audio::start_worker();
+        audio::update_warm_capture(&audio_tx, &settings.audio);
+        let job_tx = jobs::start_worker();
         let ui_active = Arc::new(AtomicBool::new(false));
 
         Self {
@@ -37,10 +57,17 @@ impl AppState {
             recording: false,
             recording_started_at: None,
             recording_started_at_ms: None,
+            recording_paused: false,
             preview_cancel: None,
+            preview_prefix: None,
             ui_active,
             audio_tx,
+            job_tx,
+            jobs: Vec::new(),
             last_focus_window: None,
+            recording_translate: None,
+            transcription_cancels: HashMap::new(),
+            previous_dictation: None,
         }
     }
 }
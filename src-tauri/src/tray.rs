@@ -19,6 +19,7 @@ mod linux_tray {
         record_toggle: String,
         paste_last: String,
         open_app: String,
+        pause_toggle: String,
     }
 
     #[derive(Serialize)]
@@ -105,6 +106,7 @@ mod linux_tray {
             record_toggle: settings.hotkeys.record_toggle.clone(),
             paste_last: settings.hotkeys.paste_last.clone(),
             open_app: settings.hotkeys.open_app.clone(),
+            pause_toggle: settings.hotkeys.pause_toggle.clone(),
         };
 
         let state = TrayState {
@@ -14,10 +14,123 @@ pub struct Transcript {
     pub tags: Vec<String>,
     #[serde(default)]
     pub audio_path: Option<String>,
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub language_probabilities: Vec<LanguageProbability>,
+    /// True when the text is an English translation; `language` is then the source language.
+    #[serde(default)]
+    pub translated: bool,
+    /// Segments the hallucination filter removed from `text`.
+    #[serde(default)]
+    pub filtered: Vec<FilteredSegment>,
+    /// User-chosen names by speaker index; missing or empty entries show as "Speaker N".
+    #[serde(default)]
+    pub speaker_names: Vec<String>,
+    /// Replacement rules that changed `text`.
+    #[serde(default)]
+    pub replacements: Vec<AppliedReplacement>,
+    /// Words that sound like custom vocabulary, whether or not they were rewritten.
+    #[serde(default)]
+    pub vocab_suggestions: Vec<VocabSuggestion>,
     #[serde(skip)]
     pub embedding: Option<Vec<f32>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    /// 0-based speaker index when speaker detection ran.
+    #[serde(default)]
+    pub speaker: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageProbability {
+    pub language: String,
+    pub probability: f32,
+}
+
+/// Whisper output dropped as a likely hallucination. `reason` is "no_speech", "known_phrase" or
+/// "repetition".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilteredSegment {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub reason: String,
+}
+
+/// A user-defined correction applied to transcripts (see `core::replacements`). `mode` is
+/// "literal", "whole_word" or "regex".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplacementRule {
+    pub id: String,
+    pub pattern: String,
+    pub replacement: String,
+    pub mode: String,
+    pub case_sensitive: bool,
+    /// Match the replacement's casing to the matched text (all caps, capitalised).
+    pub preserve_case: bool,
+    pub enabled: bool,
+}
+
+/// A replacement rule that fired on a transcript and how many times.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedReplacement {
+    pub rule_id: String,
+    pub pattern: String,
+    pub replacement: String,
+    pub count: u32,
+}
+
+/// Transcript words that sound like a custom vocabulary term (see `core::vocabulary`). `applied`
+/// means `original` was already replaced by `suggestion` in the text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VocabSuggestion {
+    pub original: String,
+    pub suggestion: String,
+    pub score: f32,
+    pub applied: bool,
+}
+
+/// A run of consecutive low-confidence words; `word_start..word_end` indexes `Transcript::words`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceSpan {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    pub confidence: f32,
+    pub word_start: usize,
+    pub word_end: usize,
+}
+
+/// A spoken phrase and what it does to dictated text (see `core::dictation`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictationCommand {
+    pub id: String,
+    pub language: String,
+    pub phrase: String,
+    pub action: String,
+    /// Text inserted by `insert` and `open` commands.
+    #[serde(default)]
+    pub value: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Clip {
     pub id: String,
@@ -37,14 +150,78 @@ pub struct PerformanceInfo {
     pub gpu_error: Option<String>,
     #[serde(default)]
     pub gpu_name: Option<String>,
+    /// Models with a loaded context, most recently used first.
+    #[serde(default)]
+    pub resident_models: Vec<ResidentModel>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResidentModel {
+    pub model: String,
+    pub size_bytes: u64,
+    pub gpu: bool,
+}
+
+/// Speed of one model, thread count and CPU/GPU combination on this machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BenchmarkResult {
+    pub id: String,
+    /// Shared by the combinations of one benchmark run.
+    pub run_id: String,
+    pub created_at: i64,
+    pub model: String,
+    pub threads: u32,
+    /// Whether the GPU was actually used (false after a CPU fallback).
+    pub gpu: bool,
+    /// Time to load the model from disk into a fresh context.
+    pub cold_load_ms: u64,
+    pub audio_seconds: f32,
+    /// Transcription time with the model already loaded.
+    pub duration_ms: u64,
+    pub realtime_factor: f32,
+    /// Highest resident memory during the run, where the platform reports it.
+    pub peak_memory_bytes: Option<u64>,
+    pub gpu_name: Option<String>,
+    pub cpu_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub results: Vec<BenchmarkResult>,
+    /// Best threads and CPU/GPU choice for the configured model.
+    pub recommended: Option<BenchmarkResult>,
+}
+
+/// Accuracy and speed of one model and decoding preset over a set of reference recordings.
+/// Error counts are summed over all files, so `wer` weights long recordings more.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationResult {
+    pub id: String,
+    /// Shared by the results of one evaluation run.
+    pub run_id: String,
+    pub created_at: i64,
+    pub model: String,
+    pub preset: String,
+    /// Custom vocabulary in effect, to compare vocabulary changes between runs.
+    pub custom_vocab: String,
+    pub wer: f32,
+    pub cer: f32,
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    pub reference_words: usize,
     pub audio_seconds: f32,
     pub duration_ms: u64,
     pub realtime_factor: f32,
-    pub text_length: usize,
+    pub files: Vec<EvaluationFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationFile {
+    pub name: String,
+    pub wer: f32,
+    pub cer: f32,
+    pub hypothesis: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +240,7 @@ pub struct ImportResult {
 pub struct ModelInfo {
     pub id: String,
     pub label: String,
+    pub multilingual: bool,
     pub installed: bool,
     pub active: bool,
 }
@@ -70,7 +248,36 @@ pub struct ModelInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToggleResult {
     pub recording: bool,
-    pub transcript: Option<Transcript>,
+    /// Transcription queued by stopping a recording; the transcript arrives via events.
+    pub job: Option<TranscriptionJob>,
+}
+
+/// File transcriptions that `cancel_transcription` can stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionTask {
+    Import,
+    Evaluation,
+    Benchmark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionJob {
+    pub id: String,
+    pub status: JobStatus,
+    pub created_at: i64,
+    pub duration_ms: u32,
+    pub transcript_id: Option<String>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
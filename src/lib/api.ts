@@ -12,19 +12,50 @@ export type Settings = {
     vad_threshold: number;
     vad_silence_ms: number;
     vad_resume_ms: number;
+    long_recording: boolean;
+    auto_stop: boolean;
+    auto_stop_silence_ms: number;
+    auto_stop_no_speech_ms: number;
+    pre_roll: boolean;
+    pre_roll_ms: number;
   };
   hotkeys: {
     record_toggle: string;
     paste_last: string;
     open_app: string;
+    record_translate: string;
+    pause_toggle: string;
+    push_to_talk: boolean;
+    push_to_talk_min_hold_ms: number;
   };
   transcription: {
+    backend: 'whisper' | 'http';
+    server_url: string;
+    server_model: string;
     model: string;
     model_dir: string;
     threads: number;
     language: string;
+    translate: boolean;
     custom_vocab: string;
+    vocab_correction: boolean;
+    vocab_correction_threshold: number;
     use_gpu: boolean;
+    context_cache_mb: number;
+    decoding_preset: 'fast' | 'accurate' | 'custom';
+    beam_size: number;
+    beam_patience: number;
+    temperature: number;
+    temperature_increment: number;
+    entropy_threshold: number;
+    logprob_threshold: number;
+    no_speech_threshold: number;
+    filter_hallucinations: boolean;
+    detect_speakers: boolean;
+    dictation_languages: string[];
+    inverse_text_normalization: boolean;
+    continuation_context: boolean;
+    continuation_window_secs: number;
   };
   automation: {
     auto_paste_enabled: boolean;
@@ -60,6 +91,64 @@ export type Transcript = {
   summary: string | null;
   tags: string[];
   audio_path?: string | null;
+  segments?: TranscriptSegment[];
+  words?: TranscriptWord[];
+  language?: string | null;
+  language_probabilities?: LanguageProbability[];
+  translated?: boolean;
+  filtered?: FilteredSegment[];
+  speaker_names?: string[];
+  replacements?: AppliedReplacement[];
+  vocab_suggestions?: VocabSuggestion[];
+};
+
+export type VocabSuggestion = {
+  original: string;
+  suggestion: string;
+  score: number;
+  applied: boolean;
+};
+
+export type AppliedReplacement = {
+  rule_id: string;
+  pattern: string;
+  replacement: string;
+  count: number;
+};
+
+export type FilteredSegment = {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+  reason: 'no_speech' | 'known_phrase' | 'repetition';
+};
+
+export type LanguageProbability = {
+  language: string;
+  probability: number;
+};
+
+export type TranscriptSegment = {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+  speaker?: number | null;
+};
+
+export type TranscriptWord = {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+  confidence: number;
+};
+
+export type ConfidenceSpan = {
+  start_ms: number;
+  end_ms: number;
+  text: string;
+  confidence: number;
+  word_start: number;
+  word_end: number;
 };
 
 export type TranscriptUpdate = {
@@ -67,6 +156,7 @@ export type TranscriptUpdate = {
   title?: string | null;
   summary?: string | null;
   tags?: string[];
+  speaker_names?: string[];
 };
 
 export type Clip = {
@@ -77,16 +167,57 @@ export type Clip = {
   transcript_id?: string | null;
 };
 
+export type DictationAction =
+  | 'insert'
+  | 'open'
+  | 'new_line'
+  | 'new_paragraph'
+  | 'scratch_that'
+  | 'caps_on'
+  | 'caps_off';
+
+export type DictationCommand = {
+  id: string;
+  language: string;
+  phrase: string;
+  action: DictationAction;
+  value: string;
+};
+
+export type ReplacementMode = 'literal' | 'whole_word' | 'regex';
+
+export type ReplacementRule = {
+  id: string;
+  pattern: string;
+  replacement: string;
+  mode: ReplacementMode;
+  case_sensitive: boolean;
+  preserve_case: boolean;
+  enabled: boolean;
+};
+
 export type ModelInfo = {
   id: string;
   label: string;
+  multilingual: boolean;
   installed: boolean;
   active: boolean;
 };
 
+export type JobStatus = 'queued' | 'running' | 'done' | 'failed';
+
+export type TranscriptionJob = {
+  id: string;
+  status: JobStatus;
+  created_at: number;
+  duration_ms: number;
+  transcript_id: string | null;
+  error: string | null;
+};
+
 export type ToggleResult = {
   recording: boolean;
-  transcript: Transcript | null;
+  job: TranscriptionJob | null;
 };
 
 export type RuntimeInfo = {
@@ -114,13 +245,60 @@ export type PerformanceInfo = {
   thread_count: number;
   gpu_error?: string | null;
   gpu_name?: string | null;
+  resident_models?: ResidentModel[];
+};
+
+export type ResidentModel = {
+  model: string;
+  size_bytes: number;
+  gpu: boolean;
 };
 
 export type BenchmarkResult = {
+  id: string;
+  run_id: string;
+  created_at: number;
+  model: string;
+  threads: number;
+  gpu: boolean;
+  cold_load_ms: number;
+  audio_seconds: number;
+  duration_ms: number;
+  realtime_factor: number;
+  peak_memory_bytes: number | null;
+  gpu_name: string | null;
+  cpu_count: number;
+};
+
+export type BenchmarkReport = {
+  results: BenchmarkResult[];
+  recommended: BenchmarkResult | null;
+};
+
+export type EvaluationFile = {
+  name: string;
+  wer: number;
+  cer: number;
+  hypothesis: string;
+};
+
+export type EvaluationResult = {
+  id: string;
+  run_id: string;
+  created_at: number;
+  model: string;
+  preset: string;
+  custom_vocab: string;
+  wer: number;
+  cer: number;
+  substitutions: number;
+  deletions: number;
+  insertions: number;
+  reference_words: number;
   audio_seconds: number;
   duration_ms: number;
   realtime_factor: number;
-  text_length: number;
+  files: EvaluationFile[];
 };
 
 export type ImportFailure = {
@@ -154,8 +332,10 @@ export const setAudioInputDevice = (inputDeviceId: string) =>
   invoke<Settings>('set_audio_input_device', { inputDeviceId });
 
 export const listTranscripts = () => invoke<Transcript[]>('list_transcripts');
-export const searchTranscripts = (query: string, limit?: number) =>
-  invoke<Transcript[]>('search_transcripts', { query, limit });
+export const searchTranscripts = (query: string, limit?: number, language?: string) =>
+  invoke<Transcript[]>('search_transcripts', { query, limit, language });
+export const getLowConfidenceSpans = (id: string, threshold?: number) =>
+  invoke<ConfidenceSpan[]>('get_low_confidence_spans', { id, threshold });
 export const updateTranscript = (id: string, update: TranscriptUpdate) =>
   invoke<Transcript>('update_transcript', { id, update });
 export const deleteTranscript = (id: string) => invoke<boolean>('delete_transcript', { id });
@@ -164,6 +344,10 @@ export const exportTranscript = (path: string, text: string) =>
 export const clearTranscripts = () => invoke<boolean>('clear_transcripts');
 export const importAudioFiles = (paths: string[]) =>
   invoke<ImportResult>('import_audio_files', { paths });
+export type TranscriptionTask = 'import' | 'evaluation' | 'benchmark';
+
+export const cancelTranscription = (task?: TranscriptionTask) =>
+  invoke<boolean>('cancel_transcription', { task: task ?? null });
 export const getRuntimeInfo = () => invoke<RuntimeInfo>('get_runtime_info');
 export const getMacosPermissions = () => invoke<MacosPermissions>('get_macos_permissions');
 export const requestMacosAccessibilityPermission = () =>
@@ -174,12 +358,28 @@ export const openMacosPermissionSettings = (permission: 'accessibility' | 'input
   invoke<boolean>('open_macos_permission_settings', { permission });
 export const getPerformanceInfo = () => invoke<PerformanceInfo>('get_performance_info');
 export const benchmarkTranscription = (path: string) =>
-  invoke<BenchmarkResult>('benchmark_transcription', { path });
+  invoke<BenchmarkReport>('benchmark_transcription', { path });
+export const listBenchmarks = () => invoke<BenchmarkResult[]>('list_benchmarks');
+export const evaluateTranscription = (paths: string[]) =>
+  invoke<EvaluationResult[]>('evaluate_transcription', { paths });
+export const listEvaluations = () => invoke<EvaluationResult[]>('list_evaluations');
 export const listAudioDevices = () => invoke<AudioDevice[]>('list_audio_devices');
-export const toggleRecording = () => invoke<ToggleResult>('toggle_recording');
+export const toggleRecording = (translate?: boolean) =>
+  invoke<ToggleResult>('toggle_recording', { translate });
+export const listTranscriptionJobs = () =>
+  invoke<TranscriptionJob[]>('list_transcription_jobs');
 export const getRecordingLevel = () => invoke<number | null>('get_recording_level');
+export const startRecording = (translate?: boolean) =>
+  invoke<ToggleResult>('start_recording', { translate });
+export const stopRecording = (translate?: boolean) =>
+  invoke<ToggleResult>('stop_recording', { translate });
+export const pauseRecording = () => invoke<boolean>('pause_recording');
+export const resumeRecording = () => invoke<boolean>('resume_recording');
+export const togglePause = () => invoke<boolean>('toggle_pause');
 export const getRecordingState = () =>
-  invoke<{ recording: boolean; started_at_ms: number | null; hud_enabled: boolean }>('get_recording_state');
+  invoke<{ recording: boolean; paused: boolean; started_at_ms: number | null; hud_enabled: boolean }>(
+    'get_recording_state'
+  );
 export const pasteLastTranscript = () => invoke<boolean>('paste_last_transcript');
 export const copyText = (text: string) => invoke<boolean>('copy_text', { text });
 export const checkForUpdates = () => invoke<UpdateInfo | null>('check_for_updates');
@@ -188,6 +388,16 @@ export const listClips = () => invoke<Clip[]>('list_clips');
 export const createClip = (title: string, text: string, transcriptId?: string | null) =>
   invoke<Clip>('create_clip', { title, text, transcriptId });
 export const deleteClip = (id: string) => invoke<boolean>('delete_clip', { id });
+export const listDictationCommands = () => invoke<DictationCommand[]>('list_dictation_commands');
+export const saveDictationCommand = (command: DictationCommand) =>
+  invoke<DictationCommand>('save_dictation_command', { command });
+export const deleteDictationCommand = (id: string) =>
+  invoke<boolean>('delete_dictation_command', { id });
+export const listReplacementRules = () => invoke<ReplacementRule[]>('list_replacement_rules');
+export const saveReplacementRule = (rule: ReplacementRule) =>
+  invoke<ReplacementRule>('save_replacement_rule', { rule });
+export const deleteReplacementRule = (id: string) =>
+  invoke<boolean>('delete_replacement_rule', { id });
 
 export const listModels = () => invoke<ModelInfo[]>('list_models');
 export const downloadModel = (modelId: string) =>
@@ -4,6 +4,10 @@ import type { Settings } from './api';
 
 export type HotkeyHandlers = {
   onToggle: () => Promise<void>;
+  onStart: () => Promise<void>;
+  onStop: () => Promise<void>;
+  onToggleTranslate: () => Promise<void>;
+  onTogglePause: () => Promise<void>;
   onPasteLast: () => Promise<void>;
 };
 
@@ -61,6 +65,16 @@ export const validateHotkeys = (settings: Settings): string | null => {
     ['Open app', normalizeHotkeyString(settings.hotkeys.open_app)],
   ];
 
+  // Optional: only validated once the user has assigned one.
+  const translateCombo = normalizeHotkeyString(settings.hotkeys.record_translate ?? '');
+  if (translateCombo) {
+    entries.push(['Record & translate', translateCombo]);
+  }
+  const pauseCombo = normalizeHotkeyString(settings.hotkeys.pause_toggle ?? '');
+  if (pauseCombo) {
+    entries.push(['Pause/resume', pauseCombo]);
+  }
+
   for (const [label, combo] of entries) {
     if (!combo) {
       return `${label} hotkey is required.`;
@@ -94,10 +108,31 @@ export const registerHotkeys = async (settings: Settings, handlers: HotkeyHandle
   await unregisterAll();
 
   await register(normalizeHotkeyString(settings.hotkeys.record_toggle), async (event) => {
+    if (settings.hotkeys.push_to_talk) {
+      // Hold to record, release to transcribe.
+      await (event.state === 'Pressed' ? handlers.onStart() : handlers.onStop());
+      return;
+    }
     if (event.state !== 'Pressed') return;
     await handlers.onToggle();
   });
 
+  const translateCombo = normalizeHotkeyString(settings.hotkeys.record_translate ?? '');
+  if (translateCombo) {
+    await register(translateCombo, async (event) => {
+      if (event.state !== 'Pressed') return;
+      await handlers.onToggleTranslate();
+    });
+  }
+
+  const pauseCombo = normalizeHotkeyString(settings.hotkeys.pause_toggle ?? '');
+  if (pauseCombo) {
+    await register(pauseCombo, async (event) => {
+      if (event.state !== 'Pressed') return;
+      await handlers.onTogglePause();
+    });
+  }
+
   await register(normalizeHotkeyString(settings.hotkeys.paste_last), async (event) => {
     if (event.state !== 'Pressed') return;
     await handlers.onPasteLast();